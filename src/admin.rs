@@ -0,0 +1,82 @@
+//! Operational endpoints under `/admin`, guarded by the bearer token from
+//! `ADMIN_TOKEN`. They are only mounted when a token is configured.
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson};
+use crate::logging::LogFilter;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogLevel {
+    /// `EnvFilter` directives, e.g. `info,rust_ai_pills_blog::database=debug`.
+    pub directives: String,
+}
+
+/// Rejects requests without `Authorization: Bearer <ADMIN_TOKEN>`.
+pub async fn require_admin_token(
+    State(token): State<Arc<String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()));
+
+    if authorized {
+        next.run(request).await
+    } else {
+        tracing::warn!(
+            "Admin: Rejected unauthenticated request to {}",
+            request.uri()
+        );
+        ApiError::unauthorized("unauthorized", "A valid admin bearer token is required")
+            .into_response()
+    }
+}
+
+/// Compares without stopping at the first difference, so response times
+/// don't reveal how much of a guessed token was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+pub async fn get_log_level_handler(State(log_filter): State<LogFilter>) -> Json<LogLevel> {
+    Json(LogLevel {
+        directives: log_filter.directives(),
+    })
+}
+
+/// Replaces the log filter of the running process. The change lasts until
+/// the next restart, which goes back to the configured `logging.level`.
+pub async fn set_log_level_handler(
+    State(log_filter): State<LogFilter>,
+    ApiJson(payload): ApiJson<LogLevel>,
+) -> Result<Json<LogLevel>, ApiError> {
+    let previous = log_filter.directives();
+
+    log_filter.set(&payload.directives).map_err(|e| {
+        ApiError::unprocessable(
+            "invalid_log_filter",
+            "Directives are not a valid log filter",
+        )
+        .with_extension("reason", e)
+    })?;
+
+    let directives = log_filter.directives();
+    tracing::warn!(
+        "Admin: Log filter changed from '{}' to '{}'",
+        previous,
+        directives
+    );
+    Ok(Json(LogLevel { directives }))
+}
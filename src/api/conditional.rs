@@ -0,0 +1,147 @@
+//! Optimistic concurrency over HTTP: resources expose their version as a
+//! strong `ETag`, and mutating requests may send it back in `If-Match`.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue},
+    response::{IntoResponseParts, ResponseParts},
+};
+use std::convert::Infallible;
+
+use crate::api::ApiError;
+
+/// Sets the `ETag` header to the given resource version, e.g. `"3"`.
+pub struct ETag(pub u64);
+
+impl IntoResponseParts for ETag {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let value = HeaderValue::from_str(&format!("\"{}\"", self.0))
+            .expect("a quoted number is a valid header value");
+        res.headers_mut().insert(header::ETAG, value);
+        Ok(res)
+    }
+}
+
+/// The version a client expects the resource to be at, taken from `If-Match`.
+/// `None` when the header is absent or `*`, in which case any version matches.
+///
+/// Only a single strong entity tag is accepted. Tags this API never issues,
+/// weak ones included, can't match and are rejected with 412 right away.
+pub struct IfMatch(pub Option<u64>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(Self(None));
+        };
+
+        let value = value.to_str().map_err(|_| invalid_if_match())?.trim();
+        if value == "*" {
+            return Ok(Self(None));
+        }
+        if value.contains(',') {
+            return Err(invalid_if_match());
+        }
+
+        value
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .and_then(|version| version.parse::<u64>().ok())
+            .map(|version| Self(Some(version)))
+            .ok_or_else(|| {
+                ApiError::precondition_failed(
+                    "precondition_failed",
+                    "If-Match does not match the current version of the resource",
+                )
+            })
+    }
+}
+
+fn invalid_if_match() -> ApiError {
+    ApiError::bad_request(
+        "invalid_if_match",
+        "If-Match must be a single entity tag or *",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::Request, http::StatusCode, response::IntoResponse};
+    use std::sync::Arc;
+
+    use crate::pills::application::{UpdatePillCommand, UpdatePillCommandHandler};
+    use crate::pills::domain::{Pill, PillId, PillRepository};
+    use crate::pills::infrastructure::persistense::in_memory_repository::InMemoryPillRepository;
+
+    async fn if_match(value: Option<&str>) -> Result<Option<u64>, StatusCode> {
+        let mut request = Request::builder();
+        if let Some(value) = value {
+            request = request.header(header::IF_MATCH, value);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        IfMatch::from_request_parts(&mut parts, &())
+            .await
+            .map(|IfMatch(version)| version)
+            .map_err(|e| e.into_response().status())
+    }
+
+    #[tokio::test]
+    async fn absent_or_wildcard_matches_any_version() {
+        assert_eq!(if_match(None).await, Ok(None));
+        assert_eq!(if_match(Some("*")).await, Ok(None));
+        assert_eq!(if_match(Some(" * ")).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn strong_tag_is_parsed_as_version() {
+        assert_eq!(if_match(Some("\"3\"")).await, Ok(Some(3)));
+        assert_eq!(if_match(Some(" \"0\" ")).await, Ok(Some(0)));
+    }
+
+    #[tokio::test]
+    async fn tags_never_issued_fail_the_precondition() {
+        let failed = Err(StatusCode::PRECONDITION_FAILED);
+        assert_eq!(if_match(Some("W/\"3\"")).await, failed);
+        assert_eq!(if_match(Some("3")).await, failed);
+        assert_eq!(if_match(Some("\"abc\"")).await, failed);
+        assert_eq!(if_match(Some("\"-1\"")).await, failed);
+    }
+
+    #[tokio::test]
+    async fn lists_are_rejected() {
+        let invalid = Err(StatusCode::BAD_REQUEST);
+        assert_eq!(if_match(Some("\"1\", \"2\"")).await, invalid);
+        assert_eq!(if_match(Some("*, \"2\"")).await, invalid);
+    }
+
+    #[tokio::test]
+    async fn stale_version_fails_with_412() {
+        let repository = Arc::new(InMemoryPillRepository::new());
+        let mut pill = Pill::create(PillId::new(), "Title".into(), "Content".into());
+        repository.save(&mut pill).await.unwrap();
+        repository.save(&mut pill).await.unwrap();
+        let handler = UpdatePillCommandHandler::new(repository);
+
+        let stale = if_match(Some("\"1\"")).await.unwrap();
+        let command = UpdatePillCommand::new(pill.id(), Some("Renamed".into()), None)
+            .with_expected_version(stale);
+        let error = handler.handle(command).await.unwrap_err();
+        assert_eq!(
+            ApiError::from(error).into_response().status(),
+            StatusCode::PRECONDITION_FAILED
+        );
+
+        let current = if_match(Some("\"2\"")).await.unwrap();
+        let command = UpdatePillCommand::new(pill.id(), Some("Renamed".into()), None)
+            .with_expected_version(current);
+        assert_eq!(handler.handle(command).await.unwrap().version(), 3);
+    }
+}
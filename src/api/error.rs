@@ -0,0 +1,221 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::api::request_id;
+use crate::courses::domain::CourseRepositoryError;
+use crate::database::DatabaseError;
+use crate::pills::domain::RepositoryError;
+use crate::search::domain::SearchRepositoryError;
+use crate::validation::ValidationErrors;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// An HTTP error rendered as an RFC 7807 `application/problem+json` body.
+///
+/// `code` is a stable, machine-readable identifier clients can branch on;
+/// `detail` is meant for humans and never carries internal error messages.
+/// `request_id` identifies the request in the logs.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+    extensions: Map<String, Value>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            detail: detail.into(),
+            extensions: Map::new(),
+        }
+    }
+
+    pub fn bad_request(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, detail)
+    }
+
+    pub fn unauthorized(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, code, detail)
+    }
+
+    pub fn not_found(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, detail)
+    }
+
+    pub fn conflict(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, code, detail)
+    }
+
+    pub fn precondition_failed(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::PRECONDITION_FAILED, code, detail)
+    }
+
+    pub fn unprocessable(code: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, code, detail)
+    }
+
+    /// Logs `error` and returns a generic 500 that doesn't reveal it.
+    pub fn internal(error: impl std::fmt::Display) -> Self {
+        tracing::error!("API: Internal error: {}", error);
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "An unexpected error occurred",
+        )
+    }
+
+    /// Adds a member to the problem body next to the standard fields.
+    pub fn with_extension(mut self, key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.extensions.insert(key.to_string(), value);
+        self
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut body = Map::new();
+        body.insert("type".to_string(), "about:blank".into());
+        body.insert(
+            "title".to_string(),
+            self.status.canonical_reason().unwrap_or("Error").into(),
+        );
+        body.insert("status".to_string(), self.status.as_u16().into());
+        body.insert("code".to_string(), self.code.into());
+        body.insert("detail".to_string(), self.detail.into());
+        if let Some(request_id) = request_id::current() {
+            body.insert("request_id".to_string(), request_id.into());
+        }
+        body.extend(self.extensions);
+
+        (
+            self.status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON)],
+            Value::Object(body).to_string(),
+        )
+            .into_response()
+    }
+}
+
+impl From<RepositoryError> for ApiError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::NotFound => Self::not_found("pill_not_found", "Pill not found"),
+            RepositoryError::InUse => Self::conflict(
+                "pill_in_use",
+                "Pill is still referenced by one or more courses. Use ?cascade=true to remove it from them",
+            ),
+            RepositoryError::InvalidCursor => {
+                Self::bad_request("invalid_cursor", "Invalid pagination cursor")
+            }
+            RepositoryError::Invalid(errors) => errors.into(),
+            RepositoryError::ConcurrencyConflict => Self::precondition_failed(
+                "concurrency_conflict",
+                "Pill was modified by another request. Fetch it again and retry",
+            ),
+            RepositoryError::Unexpected => Self::internal(error),
+        }
+    }
+}
+
+impl From<CourseRepositoryError> for ApiError {
+    fn from(error: CourseRepositoryError) -> Self {
+        match error {
+            CourseRepositoryError::NotFound => {
+                Self::not_found("course_not_found", "Course not found")
+            }
+            CourseRepositoryError::DuplicateTitle => Self::conflict(
+                "duplicate_course_title",
+                "Course with this title already exists",
+            ),
+            CourseRepositoryError::PillsNotFound(missing_pill_ids) => {
+                Self::unprocessable("pills_not_found", "Some referenced pills do not exist")
+                    .with_extension("missing_pill_ids", missing_pill_ids)
+            }
+            CourseRepositoryError::InvalidPillOrder => Self::unprocessable(
+                "invalid_pill_order",
+                "Pill order must contain exactly the pills of the course, with each section's pills together in section order. Use /courses/{id}/pills/{pill_id}/section to move a pill to another section",
+            ),
+            CourseRepositoryError::SectionNotFound => {
+                Self::not_found("section_not_found", "Section not found")
+            }
+            CourseRepositoryError::SectionNotEmpty => Self::conflict(
+                "section_not_empty",
+                "Section still contains pills. Move or remove them first",
+            ),
+            CourseRepositoryError::InvalidSectionOrder => Self::unprocessable(
+                "invalid_section_order",
+                "Section order must contain exactly the sections of the course",
+            ),
+            CourseRepositoryError::InvalidCursor => {
+                Self::bad_request("invalid_cursor", "Invalid pagination cursor")
+            }
+            CourseRepositoryError::Invalid(errors) => errors.into(),
+            CourseRepositoryError::ConcurrencyConflict => Self::precondition_failed(
+                "concurrency_conflict",
+                "Course was modified by another request. Fetch it again and retry",
+            ),
+            CourseRepositoryError::Unexpected => Self::internal(error),
+        }
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        Self::unprocessable("validation_failed", "One or more fields are invalid")
+            .with_extension("errors", errors)
+    }
+}
+
+impl From<SearchRepositoryError> for ApiError {
+    fn from(error: SearchRepositoryError) -> Self {
+        match error {
+            SearchRepositoryError::Unexpected => Self::internal(error),
+        }
+    }
+}
+
+impl From<DatabaseError> for ApiError {
+    fn from(error: DatabaseError) -> Self {
+        match error {
+            DatabaseError::DuplicateKey => {
+                Self::conflict("duplicate_key", "Resource already exists")
+            }
+            DatabaseError::ConnectionFailed(_) => {
+                tracing::error!("API: Database unavailable: {}", error);
+                Self::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "database_unavailable",
+                    "The database is temporarily unavailable",
+                )
+            }
+            _ => Self::internal(error),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(rejection.status(), "invalid_body", rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::new(rejection.status(), "invalid_path", rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(rejection.status(), "invalid_query", rejection.body_text())
+    }
+}
@@ -0,0 +1,18 @@
+//! Drop-in replacements for axum's extractors that reject with [`ApiError`],
+//! so malformed requests get the same problem+json body as every other error.
+
+use axum::extract::{FromRequest, FromRequestParts};
+
+use crate::api::ApiError;
+
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
@@ -0,0 +1,58 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Link {
+    pub href: String,
+}
+
+/// A resource with hypermedia links to itself and related resources,
+/// serialized as the resource's own fields plus a `_links` object.
+#[derive(Debug, Clone, Serialize)]
+pub struct Linked<T> {
+    #[serde(flatten)]
+    resource: T,
+    #[serde(rename = "_links")]
+    links: BTreeMap<&'static str, Link>,
+}
+
+impl<T> Linked<T> {
+    pub fn new(resource: T, self_href: impl Into<String>) -> Self {
+        let mut links = BTreeMap::new();
+        links.insert(
+            "self",
+            Link {
+                href: self_href.into(),
+            },
+        );
+        Self { resource, links }
+    }
+
+    pub fn with_link(mut self, rel: &'static str, href: impl Into<String>) -> Self {
+        self.links.insert(rel, Link { href: href.into() });
+        self
+    }
+
+    fn self_href(&self) -> &str {
+        &self.links["self"].href
+    }
+}
+
+/// `201 Created` with a `Location` header pointing at the resource's `self` link.
+pub struct Created<T>(pub Linked<T>);
+
+impl<T: Serialize> IntoResponse for Created<T> {
+    fn into_response(self) -> Response {
+        let location = self.0.self_href().to_string();
+        (
+            StatusCode::CREATED,
+            [(header::LOCATION, location)],
+            Json(self.0),
+        )
+            .into_response()
+    }
+}
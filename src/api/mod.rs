@@ -0,0 +1,10 @@
+pub mod conditional;
+pub mod error;
+pub mod extract;
+pub mod links;
+pub mod request_id;
+
+pub use conditional::{ETag, IfMatch};
+pub use error::ApiError;
+pub use extract::{ApiJson, ApiPath, ApiQuery};
+pub use links::{Created, Linked};
@@ -0,0 +1,81 @@
+//! Request correlation: every request gets an ID, taken from `X-Request-Id`
+//! or generated, that is echoed in the response, included in error bodies and
+//! attached to every log line written while handling the request.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use tracing::{field, Instrument};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longer IDs sent by clients are replaced rather than logged.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// The ID of the request being handled by the current task, if any.
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Keeps the client's `X-Request-Id` if it is a plausible ID, i.e. short and
+/// made of letters, digits and `-_.:`; otherwise generates a UUID.
+fn request_id(request: &Request) -> String {
+    request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LENGTH
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Runs the request inside a `request` span carrying its ID, method and
+/// route, records the status and latency on it once the response is ready,
+/// and sets `X-Request-Id` on the response.
+pub async fn track_request_id(request: Request, next: Next) -> Response {
+    let id = request_id(&request);
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %request.method(),
+        route = %route,
+        status = field::Empty,
+        latency_ms = field::Empty,
+    );
+
+    let started = Instant::now();
+    let mut response = REQUEST_ID
+        .scope(id.clone(), next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    span.record("status", response.status().as_u16());
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+    span.in_scope(|| tracing::info!("Request: Completed"));
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
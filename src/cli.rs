@@ -0,0 +1,104 @@
+use crate::config::DatabaseSettings;
+use crate::database::{migrations, DatabaseConfig};
+use crate::storage::StorageBackend;
+
+pub const USAGE: &str = "\
+Usage: rust_ai_pills_blog [OPTION]
+
+With no option the API server starts and applies pending migrations.
+
+Options:
+  --migrations-status    List every migration and whether it has been applied
+  --migrations-dry-run   List pending migrations and how many documents each would change
+  -h, --help             Show this help";
+
+/// What the binary was asked to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Serve,
+    Migrations(MigrationCommand),
+    Help,
+}
+
+/// A command that inspects migrations instead of starting the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationCommand {
+    Status,
+    DryRun,
+}
+
+impl Command {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let command = match args.next().as_deref() {
+            None => Self::Serve,
+            Some("--migrations-status") => Self::Migrations(MigrationCommand::Status),
+            Some("--migrations-dry-run") => Self::Migrations(MigrationCommand::DryRun),
+            Some("-h" | "--help") => Self::Help,
+            Some(other) => return Err(format!("Unknown option '{}'\n\n{}", other, USAGE)),
+        };
+
+        match args.next() {
+            None => Ok(command),
+            Some(extra) => Err(format!("Unexpected argument '{}'\n\n{}", extra, USAGE)),
+        }
+    }
+}
+
+impl MigrationCommand {
+    /// Runs the command and returns the process exit code.
+    pub async fn run(self, settings: &DatabaseSettings) -> i32 {
+        if settings.backend != StorageBackend::MongoDb {
+            eprintln!(
+                "Migrations only apply to the MongoDB backend, STORAGE_BACKEND is {:?}. \
+                 The SQLite schema, including added columns, is brought up to date at startup",
+                settings.backend
+            );
+            return 1;
+        }
+
+        match self.run_migrations(settings).await {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        }
+    }
+
+    async fn run_migrations(
+        self,
+        settings: &DatabaseSettings,
+    ) -> Result<(), crate::database::DatabaseError> {
+        let db_config = DatabaseConfig::new(settings).await?;
+        let database = db_config.get_database();
+
+        if self == Self::Status {
+            for migration in migrations::status(database).await? {
+                let state = match migration.applied_at {
+                    Some(applied_at) => format!(
+                        "applied {}",
+                        applied_at.try_to_rfc3339_string().unwrap_or_default()
+                    ),
+                    None => "pending".to_string(),
+                };
+                println!(
+                    "{:04}  {:<32}  {}",
+                    migration.version, migration.name, state
+                );
+            }
+            return Ok(());
+        }
+
+        let planned = migrations::dry_run(database).await?;
+        if planned.is_empty() {
+            println!("No pending migrations");
+        }
+        for migration in planned {
+            println!(
+                "{:04}  {:<32}  would change {} documents",
+                migration.version, migration.name, migration.documents
+            );
+        }
+        Ok(())
+    }
+}
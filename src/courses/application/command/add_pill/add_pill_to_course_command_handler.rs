@@ -20,10 +20,11 @@ impl AddPillToCourseCommandHandler {
         }
     }
 
+    /// Returns the version the course was saved at.
     pub async fn handle(
         &self,
         command: AddPillToCourseCommand,
-    ) -> Result<(), CourseRepositoryError> {
+    ) -> Result<u64, CourseRepositoryError> {
         match self.pill_repository.find_by_id(command.pill_id).await {
             Ok(Some(_)) => {
                 tracing::info!(
@@ -40,16 +41,10 @@ impl AddPillToCourseCommandHandler {
             }
         }
 
-        let mut course = self
-            .course_repository
-            .find_by_id(command.course_id)
-            .await?
-            .ok_or(CourseRepositoryError::NotFound)?;
-
-        course.add_pill(command.pill_id);
-
-        self.course_repository.save(&course).await?;
-
-        Ok(())
+        // An atomic update rather than load-modify-save, so concurrent
+        // additions to the same course can't drop each other's pills.
+        self.course_repository
+            .add_pill(command.course_id, command.pill_id, command.expected_version)
+            .await
     }
 }
@@ -1,4 +1,8 @@
-use crate::{courses::domain::course::Difficulty, pills::domain::PillId};
+use std::collections::HashSet;
+
+use crate::courses::domain::{course::Difficulty, Course};
+use crate::pills::domain::PillId;
+use crate::validation::ValidationErrors;
 
 #[derive(Debug, Clone)]
 pub struct CreateCourseCommand {
@@ -13,25 +17,25 @@ pub struct CreateCourseCommand {
 }
 
 impl CreateCourseCommand {
-    pub fn with_pills(
-        title: String,
-        description: String,
-        instructor: String,
-        pill_ids: Vec<PillId>,
-        difficulty: Difficulty,
-        hours: i8,
-        tags: Vec<String>,
-        price: f32,
-    ) -> Self {
-        Self {
-            title,
-            description,
-            instructor,
-            pill_ids,
-            difficulty,
-            hours,
-            tags,
-            price,
+    /// Also rejects a pill listed more than once, since a course holds each pill once.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Course::validate_title(&self.title, &mut errors);
+        Course::validate_description(&self.description, &mut errors);
+        Course::validate_instructor(&self.instructor, &mut errors);
+        Course::validate_hours(self.hours, &mut errors);
+        Course::validate_tags(&self.tags, &mut errors);
+        Course::validate_price(self.price, &mut errors);
+
+        let mut seen = HashSet::new();
+        for (index, pill_id) in self.pill_ids.iter().enumerate() {
+            if !seen.insert(pill_id) {
+                errors.add(
+                    format!("pill_ids[{}]", index),
+                    format!("duplicate pill '{}'", pill_id),
+                );
+            }
         }
+        errors.into_result()
     }
 }
@@ -1,40 +1,192 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::create_course_command::CreateCourseCommand;
-use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
-use crate::courses::domain::{Course, CourseId};
+use crate::courses::domain::course_repository::CourseRepositoryError;
+use crate::courses::domain::{Course, CourseDetails, CourseId};
+use crate::pills::domain::{Pill, PillId};
+use crate::unit_of_work::{Repositories, UnitOfWork};
 
 pub struct CreateCourseCommandHandler {
-    course_repository: Arc<dyn CourseRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
 }
 
 impl CreateCourseCommandHandler {
-    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
-        Self { course_repository }
+    pub fn new(unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        Self { unit_of_work }
     }
 
+    /// The title and pill checks run in the same unit of work as the save, so
+    /// they read the same snapshot the course is written against.
     pub async fn handle(
         &self,
         command: CreateCourseCommand,
-    ) -> Result<CourseId, CourseRepositoryError> {
-        if let Ok(Some(_)) = self.course_repository.find_by_title(&command.title).await {
-            return Err(CourseRepositoryError::DuplicateTitle);
-        }
+    ) -> Result<Course, CourseRepositoryError> {
+        command.validate()?;
 
-        let course_id = CourseId::new();
-        let course = Course::new(
-            course_id,
-            command.title.clone(),
-            command.description,
-            command.instructor,
-            command.difficulty,
-            command.hours,
-            command.tags,
-            command.price,
+        self.unit_of_work
+            .run(|repositories| create(repositories, command.clone()))
+            .await
+    }
+}
+
+async fn create(
+    repositories: Repositories,
+    command: CreateCourseCommand,
+) -> Result<Course, CourseRepositoryError> {
+    let existing = repositories
+        .course_repository
+        .find_by_title(&command.title)
+        .await?;
+    if existing.is_some() {
+        return Err(CourseRepositoryError::DuplicateTitle);
+    }
+
+    let found: HashSet<PillId> = repositories
+        .pill_repository
+        .find_by_ids(&command.pill_ids)
+        .await
+        .map_err(|_| {
+            tracing::error!("Handler (CreateCourse): Error fetching referenced pills");
+            CourseRepositoryError::Unexpected
+        })?
+        .iter()
+        .map(Pill::id)
+        .collect();
+    let missing_pill_ids: Vec<PillId> = command
+        .pill_ids
+        .iter()
+        .filter(|pill_id| !found.contains(pill_id))
+        .copied()
+        .collect();
+
+    if !missing_pill_ids.is_empty() {
+        tracing::warn!(
+            "Handler (CreateCourse): Rejecting course '{}', {} referenced pills not found",
+            command.title,
+            missing_pill_ids.len()
         );
+        return Err(CourseRepositoryError::PillsNotFound(missing_pill_ids));
+    }
+
+    let course_id = CourseId::new();
+    let details = CourseDetails {
+        title: command.title.clone(),
+        description: command.description,
+        instructor: command.instructor,
+        difficulty: command.difficulty,
+        hours: command.hours,
+        tags: command.tags,
+        price: command.price,
+    };
+    let mut course = Course::new(course_id, details)?;
+
+    for pill_id in command.pill_ids {
+        course.add_pill(pill_id);
+    }
+
+    repositories.course_repository.save(&mut course).await?;
+
+    Ok(course)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::courses::domain::course::Difficulty;
+    use crate::courses::infrastructure::persistence::in_memory_repository::InMemoryCourseRepository;
+    use crate::pills::domain::PillRepository;
+    use crate::pills::infrastructure::persistense::in_memory_repository::InMemoryPillRepository;
+    use crate::unit_of_work::NoopUnitOfWork;
+    use crate::validation::ValidationErrors;
+
+    struct Fixture {
+        handler: CreateCourseCommandHandler,
+        pills: Arc<InMemoryPillRepository>,
+        courses: Arc<InMemoryCourseRepository>,
+    }
+
+    fn fixture() -> Fixture {
+        let pills = Arc::new(InMemoryPillRepository::new());
+        let courses = Arc::new(InMemoryCourseRepository::new());
+        let unit_of_work = NoopUnitOfWork::new(Repositories {
+            pill_repository: pills.clone(),
+            course_repository: courses.clone(),
+        });
+        Fixture {
+            handler: CreateCourseCommandHandler::new(Arc::new(unit_of_work)),
+            pills,
+            courses,
+        }
+    }
+
+    async fn stored_pill(fixture: &Fixture) -> PillId {
+        let mut pill = Pill::create(PillId::new(), "Title".into(), "Content".into());
+        fixture.pills.save(&mut pill).await.unwrap();
+        pill.id()
+    }
+
+    fn command(pill_ids: Vec<PillId>) -> CreateCourseCommand {
+        CreateCourseCommand {
+            title: "Rust".into(),
+            description: "Learn Rust".into(),
+            instructor: "Jo".into(),
+            pill_ids,
+            difficulty: Difficulty::Beginner,
+            hours: 3,
+            tags: Vec::new(),
+            price: 5.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn repeated_pill_ids_are_rejected() {
+        let fixture = fixture();
+        let first = stored_pill(&fixture).await;
+        let second = stored_pill(&fixture).await;
+
+        let result = fixture
+            .handler
+            .handle(command(vec![first, second, first]))
+            .await;
+
+        let Err(CourseRepositoryError::Invalid(errors)) = result else {
+            panic!("expected a validation error, got {:?}", result);
+        };
+        let mut expected = ValidationErrors::new();
+        expected.add("pill_ids[2]", format!("duplicate pill '{}'", first));
+        assert_eq!(errors, expected);
+        assert!(fixture.courses.all().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_pill_ids_are_reported() {
+        let fixture = fixture();
+        let known = stored_pill(&fixture).await;
+        let unknown = PillId::new();
+
+        let result = fixture.handler.handle(command(vec![known, unknown])).await;
+
+        assert!(matches!(
+            result,
+            Err(CourseRepositoryError::PillsNotFound(ids)) if ids == vec![unknown]
+        ));
+        assert!(fixture.courses.all().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn course_keeps_pills_in_the_order_given() {
+        let fixture = fixture();
+        let first = stored_pill(&fixture).await;
+        let second = stored_pill(&fixture).await;
 
-        self.course_repository.save(&course).await?;
+        let course = fixture
+            .handler
+            .handle(command(vec![second, first]))
+            .await
+            .unwrap();
 
-        Ok(course_id)
+        assert_eq!(course.pill_ids(), &[second, first]);
     }
 }
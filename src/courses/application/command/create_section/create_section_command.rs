@@ -0,0 +1,31 @@
+use crate::courses::domain::{CourseId, Section};
+use crate::validation::ValidationErrors;
+
+#[derive(Debug, Clone)]
+pub struct CreateSectionCommand {
+    pub course_id: CourseId,
+    pub title: String,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl CreateSectionCommand {
+    pub fn new(course_id: CourseId, title: String) -> Self {
+        Self {
+            course_id,
+            title,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Section::validate_title(&self.title, &mut errors);
+        errors.into_result()
+    }
+}
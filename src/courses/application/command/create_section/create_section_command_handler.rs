@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use super::create_section_command::CreateSectionCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+use crate::courses::domain::{Section, SectionId};
+
+pub struct CreateSectionCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl CreateSectionCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the new section and the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: CreateSectionCommand,
+    ) -> Result<(Section, u64), CourseRepositoryError> {
+        command.validate()?;
+
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        let section_id = SectionId::new();
+        course.add_section(section_id, command.title);
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (CreateSection): Section {} added to course {}",
+            section_id,
+            command.course_id
+        );
+        course
+            .section(section_id)
+            .cloned()
+            .map(|section| (section, course.version()))
+            .ok_or(CourseRepositoryError::Unexpected)
+    }
+}
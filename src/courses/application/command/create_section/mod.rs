@@ -0,0 +1,6 @@
+pub mod create_section_command;
+pub mod create_section_command_handler;
+
+// Re-export commonly used items
+pub use create_section_command::CreateSectionCommand;
+pub use create_section_command_handler::CreateSectionCommandHandler;
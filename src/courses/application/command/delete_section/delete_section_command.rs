@@ -0,0 +1,24 @@
+use crate::courses::domain::{CourseId, SectionId};
+
+#[derive(Debug, Clone)]
+pub struct DeleteSectionCommand {
+    pub course_id: CourseId,
+    pub section_id: SectionId,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl DeleteSectionCommand {
+    pub fn new(course_id: CourseId, section_id: SectionId) -> Self {
+        Self {
+            course_id,
+            section_id,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+}
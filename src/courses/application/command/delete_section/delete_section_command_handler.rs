@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use super::delete_section_command::DeleteSectionCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct DeleteSectionCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl DeleteSectionCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: DeleteSectionCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        let section = course
+            .section(command.section_id)
+            .ok_or(CourseRepositoryError::SectionNotFound)?;
+
+        if !section.is_empty() {
+            return Err(CourseRepositoryError::SectionNotEmpty);
+        }
+
+        course.remove_section(command.section_id);
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (DeleteSection): Section {} removed from course {}",
+            command.section_id,
+            command.course_id
+        );
+        Ok(course.version())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod delete_section_command;
+pub mod delete_section_command_handler;
+
+// Re-export commonly used items
+pub use delete_section_command::DeleteSectionCommand;
+pub use delete_section_command_handler::DeleteSectionCommandHandler;
@@ -1,8 +1,32 @@
 pub mod add_pill;
 pub mod create;
+pub mod create_section;
+pub mod delete_section;
+pub mod move_pill;
+pub mod remove_pill;
+pub mod rename_section;
+pub mod reorder_pills;
+pub mod reorder_sections;
+pub mod update;
 
 // Re-export commonly used items from create
 pub use create::{CreateCourseCommand, CreateCourseCommandHandler};
 
 // Re-export commonly used items from add_pill
 pub use add_pill::{AddPillToCourseCommand, AddPillToCourseCommandHandler};
+
+// Re-export commonly used items from remove_pill
+pub use remove_pill::{RemovePillFromCourseCommand, RemovePillFromCourseCommandHandler};
+
+// Re-export commonly used items from reorder_pills
+pub use reorder_pills::{ReorderCoursePillsCommand, ReorderCoursePillsCommandHandler};
+
+// Re-export commonly used items from update
+pub use update::{UpdateCourseCommand, UpdateCourseCommandHandler};
+
+// Re-export commonly used items from the section commands
+pub use create_section::{CreateSectionCommand, CreateSectionCommandHandler};
+pub use delete_section::{DeleteSectionCommand, DeleteSectionCommandHandler};
+pub use move_pill::{MovePillToSectionCommand, MovePillToSectionCommandHandler};
+pub use rename_section::{RenameSectionCommand, RenameSectionCommandHandler};
+pub use reorder_sections::{ReorderSectionsCommand, ReorderSectionsCommandHandler};
@@ -0,0 +1,6 @@
+pub mod move_pill_to_section_command;
+pub mod move_pill_to_section_command_handler;
+
+// Re-export commonly used items
+pub use move_pill_to_section_command::MovePillToSectionCommand;
+pub use move_pill_to_section_command_handler::MovePillToSectionCommandHandler;
@@ -0,0 +1,36 @@
+use crate::courses::domain::{CourseId, SectionId};
+use crate::pills::domain::PillId;
+
+/// Moves a pill of the course into a section, at `position` within the section
+/// or at its end when `position` is `None`.
+#[derive(Debug, Clone)]
+pub struct MovePillToSectionCommand {
+    pub course_id: CourseId,
+    pub pill_id: PillId,
+    pub section_id: SectionId,
+    pub position: Option<usize>,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl MovePillToSectionCommand {
+    pub fn new(
+        course_id: CourseId,
+        pill_id: PillId,
+        section_id: SectionId,
+        position: Option<usize>,
+    ) -> Self {
+        Self {
+            course_id,
+            pill_id,
+            section_id,
+            position,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use super::move_pill_to_section_command::MovePillToSectionCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct MovePillToSectionCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl MovePillToSectionCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: MovePillToSectionCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if course.section(command.section_id).is_none() {
+            return Err(CourseRepositoryError::SectionNotFound);
+        }
+
+        if !course.move_pill(command.pill_id, command.section_id, command.position) {
+            return Err(CourseRepositoryError::NotFound);
+        }
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (MovePillToSection): Pill {} moved to section {} of course {}",
+            command.pill_id,
+            command.section_id,
+            command.course_id
+        );
+        Ok(course.version())
+    }
+}
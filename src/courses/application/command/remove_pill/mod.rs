@@ -0,0 +1,6 @@
+pub mod remove_pill_from_course_command;
+pub mod remove_pill_from_course_command_handler;
+
+// Re-export commonly used items
+pub use remove_pill_from_course_command::RemovePillFromCourseCommand;
+pub use remove_pill_from_course_command_handler::RemovePillFromCourseCommandHandler;
@@ -0,0 +1,25 @@
+use crate::courses::domain::CourseId;
+use crate::pills::domain::PillId;
+
+#[derive(Debug, Clone)]
+pub struct RemovePillFromCourseCommand {
+    pub course_id: CourseId,
+    pub pill_id: PillId,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl RemovePillFromCourseCommand {
+    pub fn new(course_id: CourseId, pill_id: PillId) -> Self {
+        Self {
+            course_id,
+            pill_id,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use super::remove_pill_from_course_command::RemovePillFromCourseCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct RemovePillFromCourseCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl RemovePillFromCourseCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: RemovePillFromCourseCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        let version = self
+            .course_repository
+            .remove_pill(command.course_id, command.pill_id, command.expected_version)
+            .await?;
+
+        tracing::info!(
+            "Handler (RemovePillFromCourse): Pill {} removed from course {}",
+            command.pill_id,
+            command.course_id
+        );
+        Ok(version)
+    }
+}
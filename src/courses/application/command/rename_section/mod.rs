@@ -0,0 +1,6 @@
+pub mod rename_section_command;
+pub mod rename_section_command_handler;
+
+// Re-export commonly used items
+pub use rename_section_command::RenameSectionCommand;
+pub use rename_section_command_handler::RenameSectionCommandHandler;
@@ -0,0 +1,33 @@
+use crate::courses::domain::{CourseId, Section, SectionId};
+use crate::validation::ValidationErrors;
+
+#[derive(Debug, Clone)]
+pub struct RenameSectionCommand {
+    pub course_id: CourseId,
+    pub section_id: SectionId,
+    pub title: String,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl RenameSectionCommand {
+    pub fn new(course_id: CourseId, section_id: SectionId, title: String) -> Self {
+        Self {
+            course_id,
+            section_id,
+            title,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Section::validate_title(&self.title, &mut errors);
+        errors.into_result()
+    }
+}
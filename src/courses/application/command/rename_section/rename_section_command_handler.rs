@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use super::rename_section_command::RenameSectionCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct RenameSectionCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl RenameSectionCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: RenameSectionCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        command.validate()?;
+
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if !course.rename_section(command.section_id, command.title) {
+            return Err(CourseRepositoryError::SectionNotFound);
+        }
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (RenameSection): Section {} of course {} renamed",
+            command.section_id,
+            command.course_id
+        );
+        Ok(course.version())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod reorder_course_pills_command;
+pub mod reorder_course_pills_command_handler;
+
+// Re-export commonly used items
+pub use reorder_course_pills_command::ReorderCoursePillsCommand;
+pub use reorder_course_pills_command_handler::ReorderCoursePillsCommandHandler;
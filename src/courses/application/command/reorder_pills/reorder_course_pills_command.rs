@@ -0,0 +1,25 @@
+use crate::courses::domain::CourseId;
+use crate::pills::domain::PillId;
+
+#[derive(Debug, Clone)]
+pub struct ReorderCoursePillsCommand {
+    pub course_id: CourseId,
+    pub pill_ids: Vec<PillId>,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl ReorderCoursePillsCommand {
+    pub fn new(course_id: CourseId, pill_ids: Vec<PillId>) -> Self {
+        Self {
+            course_id,
+            pill_ids,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use super::reorder_course_pills_command::ReorderCoursePillsCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct ReorderCoursePillsCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl ReorderCoursePillsCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: ReorderCoursePillsCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if !course.reorder_pills(command.pill_ids) {
+            tracing::warn!(
+                "Handler (ReorderCoursePills): Rejected order for course {}, pills or sections do not match",
+                command.course_id
+            );
+            return Err(CourseRepositoryError::InvalidPillOrder);
+        }
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (ReorderCoursePills): Pills of course {} reordered",
+            command.course_id
+        );
+        Ok(course.version())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod reorder_sections_command;
+pub mod reorder_sections_command_handler;
+
+// Re-export commonly used items
+pub use reorder_sections_command::ReorderSectionsCommand;
+pub use reorder_sections_command_handler::ReorderSectionsCommandHandler;
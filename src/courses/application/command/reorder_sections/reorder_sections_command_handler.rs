@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use super::reorder_sections_command::ReorderSectionsCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+
+pub struct ReorderSectionsCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl ReorderSectionsCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    /// Returns the version the course was saved at.
+    pub async fn handle(
+        &self,
+        command: ReorderSectionsCommand,
+    ) -> Result<u64, CourseRepositoryError> {
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if !course.reorder_sections(command.section_ids) {
+            tracing::warn!(
+                "Handler (ReorderSections): Rejected order for course {}, sections do not match",
+                command.course_id
+            );
+            return Err(CourseRepositoryError::InvalidSectionOrder);
+        }
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!(
+            "Handler (ReorderSections): Sections of course {} reordered",
+            command.course_id
+        );
+        Ok(course.version())
+    }
+}
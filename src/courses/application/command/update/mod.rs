@@ -0,0 +1,6 @@
+pub mod update_course_command;
+pub mod update_course_command_handler;
+
+// Re-export commonly used items
+pub use update_course_command::UpdateCourseCommand;
+pub use update_course_command_handler::UpdateCourseCommandHandler;
@@ -0,0 +1,39 @@
+use crate::courses::domain::{course::Difficulty, Course, CourseId};
+use crate::validation::ValidationErrors;
+
+/// Partial update of a course. Fields left as `None` keep their current value.
+#[derive(Debug, Clone)]
+pub struct UpdateCourseCommand {
+    pub course_id: CourseId,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub hours: Option<i8>,
+    pub tags: Option<Vec<String>>,
+    pub price: Option<f32>,
+    /// Version of the course the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl UpdateCourseCommand {
+    /// Checks the fields being changed; omitted fields are not validated.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Some(title) = &self.title {
+            Course::validate_title(title, &mut errors);
+        }
+        if let Some(description) = &self.description {
+            Course::validate_description(description, &mut errors);
+        }
+        if let Some(hours) = self.hours {
+            Course::validate_hours(hours, &mut errors);
+        }
+        if let Some(tags) = &self.tags {
+            Course::validate_tags(tags, &mut errors);
+        }
+        if let Some(price) = self.price {
+            Course::validate_price(price, &mut errors);
+        }
+        errors.into_result()
+    }
+}
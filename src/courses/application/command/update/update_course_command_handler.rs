@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use super::update_course_command::UpdateCourseCommand;
+use crate::courses::domain::course_repository::{CourseRepository, CourseRepositoryError};
+use crate::courses::domain::Course;
+
+pub struct UpdateCourseCommandHandler {
+    course_repository: Arc<dyn CourseRepository>,
+}
+
+impl UpdateCourseCommandHandler {
+    pub fn new(course_repository: Arc<dyn CourseRepository>) -> Self {
+        Self { course_repository }
+    }
+
+    pub async fn handle(
+        &self,
+        command: UpdateCourseCommand,
+    ) -> Result<Course, CourseRepositoryError> {
+        command.validate()?;
+
+        let mut course = self
+            .course_repository
+            .find_by_id(command.course_id)
+            .await?
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(command.expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if let Some(title) = command.title {
+            if title != course.title() {
+                let existing = self.course_repository.find_by_title(&title).await?;
+                if existing.is_some_and(|existing| existing.id() != course.id()) {
+                    return Err(CourseRepositoryError::DuplicateTitle);
+                }
+                course.update_title(title);
+            }
+        }
+
+        if let Some(description) = command.description {
+            course.update_description(description);
+        }
+
+        if let Some(difficulty) = command.difficulty {
+            course.update_difficulty(difficulty);
+        }
+
+        if let Some(hours) = command.hours {
+            course.update_hours(hours);
+        }
+
+        if let Some(price) = command.price {
+            course.update_price(price);
+        }
+
+        if let Some(tags) = command.tags {
+            course.replace_tags(tags);
+        }
+
+        self.course_repository.save(&mut course).await?;
+
+        tracing::info!("Handler (UpdateCourse): Course {} updated", course.id());
+        Ok(course)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    use crate::courses::domain::course::Difficulty;
+    use crate::courses::domain::{CourseDetails, CourseFilter, CourseId, CourseSort};
+    use crate::courses::infrastructure::persistence::in_memory_repository::InMemoryCourseRepository;
+    use crate::pagination::{Page, PageRequest};
+    use crate::pills::domain::PillId;
+
+    /// In-memory courses whose title lookup always fails.
+    struct FailingTitleLookup(InMemoryCourseRepository);
+
+    #[async_trait]
+    impl CourseRepository for FailingTitleLookup {
+        async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError> {
+            self.0.save(course).await
+        }
+
+        async fn add_pill(
+            &self,
+            course_id: CourseId,
+            pill_id: PillId,
+            expected_version: Option<u64>,
+        ) -> Result<u64, CourseRepositoryError> {
+            self.0.add_pill(course_id, pill_id, expected_version).await
+        }
+
+        async fn remove_pill(
+            &self,
+            course_id: CourseId,
+            pill_id: PillId,
+            expected_version: Option<u64>,
+        ) -> Result<u64, CourseRepositoryError> {
+            self.0
+                .remove_pill(course_id, pill_id, expected_version)
+                .await
+        }
+
+        async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError> {
+            self.0.find_by_id(id).await
+        }
+
+        async fn find_page(
+            &self,
+            filter: &CourseFilter,
+            sort: CourseSort,
+            page: &PageRequest,
+        ) -> Result<Page<Course>, CourseRepositoryError> {
+            self.0.find_page(filter, sort, page).await
+        }
+
+        async fn count(&self) -> Result<u64, CourseRepositoryError> {
+            self.0.count().await
+        }
+
+        async fn find_by_title(&self, _: &str) -> Result<Option<Course>, CourseRepositoryError> {
+            Err(CourseRepositoryError::Unexpected)
+        }
+
+        async fn find_by_pill_id(
+            &self,
+            pill_id: PillId,
+        ) -> Result<Vec<Course>, CourseRepositoryError> {
+            self.0.find_by_pill_id(pill_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn rename_fails_when_the_title_lookup_fails() {
+        let repository = Arc::new(FailingTitleLookup(InMemoryCourseRepository::new()));
+        let details = CourseDetails {
+            title: "Rust".into(),
+            description: "Learn Rust".into(),
+            instructor: "Jo".into(),
+            difficulty: Difficulty::Beginner,
+            hours: 3,
+            tags: Vec::new(),
+            price: 5.0,
+        };
+        let mut course = Course::new(CourseId::new(), details).unwrap();
+        repository.save(&mut course).await.unwrap();
+        let handler = UpdateCourseCommandHandler::new(repository.clone());
+
+        let command = UpdateCourseCommand {
+            course_id: course.id(),
+            title: Some("Go".into()),
+            description: None,
+            difficulty: None,
+            hours: None,
+            tags: None,
+            price: None,
+            expected_version: None,
+        };
+        assert!(matches!(
+            handler.handle(command).await,
+            Err(CourseRepositoryError::Unexpected)
+        ));
+
+        let stored = repository.find_by_id(course.id()).await.unwrap().unwrap();
+        assert_eq!(stored.title(), "Rust");
+        assert_eq!(stored.version(), 1);
+    }
+}
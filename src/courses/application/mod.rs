@@ -3,6 +3,14 @@ pub mod query;
 
 pub use command::{AddPillToCourseCommand, AddPillToCourseCommandHandler};
 pub use command::{CreateCourseCommand, CreateCourseCommandHandler};
+pub use command::{CreateSectionCommand, CreateSectionCommandHandler};
+pub use command::{DeleteSectionCommand, DeleteSectionCommandHandler};
+pub use command::{MovePillToSectionCommand, MovePillToSectionCommandHandler};
+pub use command::{RemovePillFromCourseCommand, RemovePillFromCourseCommandHandler};
+pub use command::{RenameSectionCommand, RenameSectionCommandHandler};
+pub use command::{ReorderCoursePillsCommand, ReorderCoursePillsCommandHandler};
+pub use command::{ReorderSectionsCommand, ReorderSectionsCommandHandler};
+pub use command::{UpdateCourseCommand, UpdateCourseCommandHandler};
 
 pub use query::{FindAllCoursesQuery, FindAllCoursesQueryHandler};
 pub use query::{FindCourseQuery, FindCourseQueryHandler};
@@ -1,7 +1,14 @@
-use crate::courses::domain::{Course, CourseRepository, CourseRepositoryError};
+use crate::courses::domain::{
+    Course, CourseFilter, CourseRepository, CourseRepositoryError, CourseSort,
+};
+use crate::pagination::{Page, PageRequest};
 use std::sync::Arc;
 
-pub struct FindAllCoursesQuery;
+pub struct FindAllCoursesQuery {
+    pub filter: CourseFilter,
+    pub sort: CourseSort,
+    pub page: PageRequest,
+}
 
 pub struct FindAllCoursesQueryHandler {
     repository: Arc<dyn CourseRepository>,
@@ -14,9 +21,12 @@ impl FindAllCoursesQueryHandler {
 
     pub async fn handle(
         &self,
-        _query: FindAllCoursesQuery,
-    ) -> Result<Vec<Course>, CourseRepositoryError> {
-        let courses = self.repository.find_all().await?;
+        query: FindAllCoursesQuery,
+    ) -> Result<Page<Course>, CourseRepositoryError> {
+        let courses = self
+            .repository
+            .find_page(&query.filter, query.sort, &query.page)
+            .await?;
 
         Ok(courses)
     }
@@ -1,12 +1,16 @@
 use crate::courses::domain::{Course, CourseId, CourseRepository, CourseRepositoryError};
-use crate::pills::domain::{Pill, PillRepository, RepositoryError};
+use crate::pills::domain::{Pill, PillId, PillRepository};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CourseWithPills {
     pub course: Course,
+    /// Pills in curriculum order.
     pub pills: Vec<Pill>,
+    /// Pills referenced by the course that no longer exist.
+    pub missing_pill_ids: Vec<PillId>,
 }
 
 pub struct FindCourseWithPillsQuery {
@@ -39,33 +43,37 @@ impl FindCourseWithPillsQueryHandler {
             .await?
             .ok_or(CourseRepositoryError::NotFound)?;
 
-        let mut pills = Vec::new();
+        let mut found: HashMap<PillId, Pill> = self
+            .pill_repository
+            .find_by_ids(course.pill_ids())
+            .await
+            .map_err(|_| {
+                tracing::error!("Error fetching pills of course {}", course.id());
+                CourseRepositoryError::Unexpected
+            })?
+            .into_iter()
+            .map(|pill| (pill.id(), pill))
+            .collect();
+
+        let mut pills = Vec::with_capacity(course.pill_count());
+        let mut missing_pill_ids = Vec::new();
         for pill_id in course.pill_ids() {
-            match self.pill_repository.find_by_id(*pill_id).await {
-                Ok(Some(pill)) => {
-                    pills.push(pill);
-                }
-                Ok(None) => {
-                    tracing::warn!(
-                        "Warning: Pill {} referenced by course but not found",
-                        pill_id
-                    );
-                    // Continue processing other pills instead of failing
-                }
-                Err(RepositoryError::NotFound) => {
+            match found.remove(pill_id) {
+                Some(pill) => pills.push(pill),
+                None => {
                     tracing::warn!(
                         "Warning: Pill {} referenced by course but not found",
                         pill_id
                     );
-                    // Continue processing other pills instead of failing
-                }
-                Err(_) => {
-                    tracing::error!("Error fetching pill {}", pill_id);
-                    return Err(CourseRepositoryError::Unexpected);
+                    missing_pill_ids.push(*pill_id);
                 }
             }
         }
 
-        Ok(CourseWithPills { course, pills })
+        Ok(CourseWithPills {
+            course,
+            pills,
+            missing_pill_ids,
+        })
     }
 }
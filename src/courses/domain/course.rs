@@ -1,5 +1,8 @@
+use crate::courses::domain::section::{Section, SectionId};
 use crate::pills::domain::PillId;
+use crate::validation::ValidationErrors;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -74,64 +77,148 @@ impl fmt::Display for Difficulty {
     }
 }
 
+/// Title given to the section that holds the pills of courses created before
+/// sections existed, or of courses where pills were added without one.
+pub const DEFAULT_SECTION_TITLE: &str = "Default";
+
+pub const MAX_TITLE_LENGTH: usize = 200;
+pub const MAX_DESCRIPTION_LENGTH: usize = 5_000;
+pub const MAX_INSTRUCTOR_LENGTH: usize = 100;
+pub const MAX_TAG_LENGTH: usize = 50;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Course {
     id: CourseId,
     title: String,
     description: String,
     instructor: String,
+    /// Flattened curriculum, always derived from `sections` in order.
     pill_ids: Vec<PillId>,
+    sections: Vec<Section>,
     difficulty: Difficulty,
     hours: i8,
     tags: Vec<String>,
     price: f32,
+    /// Number of times the course has been saved; 0 until it is first stored.
+    #[serde(default)]
+    version: u64,
+}
+
+/// The descriptive attributes of a course: everything but its ID, curriculum
+/// and version.
+#[derive(Debug, Clone)]
+pub struct CourseDetails {
+    pub title: String,
+    pub description: String,
+    pub instructor: String,
+    pub difficulty: Difficulty,
+    pub hours: i8,
+    pub tags: Vec<String>,
+    pub price: f32,
 }
 
 impl Course {
-    pub fn create(
-        id: CourseId,
-        title: String,
-        description: String,
-        instructor: String,
-        difficulty: Difficulty,
-        hours: i8,
-        tags: Vec<String>,
-        price: f32,
-        pill_ids: Vec<PillId>,
-    ) -> Self {
-        Self {
-            id,
+    pub fn create(id: CourseId, details: CourseDetails, mut sections: Vec<Section>) -> Self {
+        sections.sort_by_key(|section| section.position());
+
+        let CourseDetails {
             title,
             description,
             instructor,
-            pill_ids,
             difficulty,
             hours,
             tags,
             price,
-        }
-    }
-
-    pub fn new(
-        id: CourseId,
-        title: String,
-        description: String,
-        instructor: String,
-        difficulty: Difficulty,
-        hours: i8,
-        tags: Vec<String>,
-        price: f32,
-    ) -> Self {
-        Self::create(
+        } = details;
+        let mut course = Self {
             id,
             title,
             description,
             instructor,
+            pill_ids: Vec::new(),
+            sections,
             difficulty,
             hours,
             tags,
             price,
-            Vec::new(),
+            version: 0,
+        };
+        course.sync_sections();
+        course
+    }
+
+    /// Creates a new course without sections, checking every attribute
+    /// against the course rules and reporting all violations together.
+    pub fn new(id: CourseId, details: CourseDetails) -> Result<Self, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Self::validate_title(&details.title, &mut errors);
+        Self::validate_description(&details.description, &mut errors);
+        Self::validate_instructor(&details.instructor, &mut errors);
+        Self::validate_hours(details.hours, &mut errors);
+        Self::validate_tags(&details.tags, &mut errors);
+        Self::validate_price(details.price, &mut errors);
+        errors.into_result()?;
+
+        Ok(Self::create(id, details, Vec::new()))
+    }
+
+    pub fn validate_title(title: &str, errors: &mut ValidationErrors) {
+        errors.check_text("title", title, MAX_TITLE_LENGTH);
+    }
+
+    pub fn validate_description(description: &str, errors: &mut ValidationErrors) {
+        if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+            errors.add(
+                "description",
+                format!("must be at most {} characters", MAX_DESCRIPTION_LENGTH),
+            );
+        }
+    }
+
+    pub fn validate_instructor(instructor: &str, errors: &mut ValidationErrors) {
+        errors.check_text("instructor", instructor, MAX_INSTRUCTOR_LENGTH);
+    }
+
+    pub fn validate_hours(hours: i8, errors: &mut ValidationErrors) {
+        if hours <= 0 {
+            errors.add("hours", "must be greater than 0");
+        }
+    }
+
+    pub fn validate_price(price: f32, errors: &mut ValidationErrors) {
+        if !price.is_finite() || price < 0.0 {
+            errors.add("price", "must be 0 or greater");
+        }
+    }
+
+    /// Tags must be non-blank and unique, ignoring case and surrounding whitespace.
+    /// Errors point at the offending entry, e.g. `tags[2]`.
+    pub fn validate_tags(tags: &[String], errors: &mut ValidationErrors) {
+        let mut seen = HashSet::new();
+        for (index, tag) in tags.iter().enumerate() {
+            let field = format!("tags[{}]", index);
+            let normalized = tag.trim().to_lowercase();
+            if normalized.is_empty() {
+                errors.add(field, "must not be blank");
+            } else if tag.chars().count() > MAX_TAG_LENGTH {
+                errors.add(
+                    field,
+                    format!("must be at most {} characters", MAX_TAG_LENGTH),
+                );
+            } else if !seen.insert(normalized) {
+                errors.add(field, format!("duplicate tag '{}'", tag.trim()));
+            }
+        }
+    }
+
+    /// The section used for pills that don't belong to an explicit section.
+    /// Its ID is derived from the course ID so it stays stable across reads.
+    pub fn default_section(id: CourseId, pill_ids: Vec<PillId>) -> Section {
+        Section::create(
+            SectionId::from(id.0),
+            DEFAULT_SECTION_TITLE.to_string(),
+            0,
+            pill_ids,
         )
     }
 
@@ -155,6 +242,14 @@ impl Course {
         &self.pill_ids
     }
 
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn section(&self, section_id: SectionId) -> Option<&Section> {
+        self.sections.iter().find(|s| s.id() == section_id)
+    }
+
     pub fn difficulty(&self) -> Difficulty {
         self.difficulty
     }
@@ -171,28 +266,189 @@ impl Course {
         self.price
     }
 
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Sets the stored version of a course loaded from a repository.
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Records the version a repository assigned when saving the course.
+    pub fn set_version(&mut self, version: u64) {
+        self.version = version;
+    }
+
+    /// Whether the course is still at the version a client last saw; no
+    /// expectation always matches.
+    pub fn matches_version(&self, expected: Option<u64>) -> bool {
+        expected.is_none_or(|expected| expected == self.version)
+    }
+
+    /// Appends the pill to the last section, creating the default section
+    /// when the course has none yet.
     pub fn add_pill(&mut self, pill_id: PillId) {
-        if !self.pill_ids.contains(&pill_id) {
-            self.pill_ids.push(pill_id);
+        if self.has_pill(pill_id) {
+            return;
         }
+
+        if self.sections.is_empty() {
+            self.sections
+                .push(Self::default_section(self.id, Vec::new()));
+        }
+
+        if let Some(section) = self.sections.last_mut() {
+            section.push_pill(pill_id);
+        }
+        self.sync_sections();
+    }
+
+    pub fn remove_pill(&mut self, pill_id: PillId) {
+        for section in &mut self.sections {
+            section.remove_pill(pill_id);
+        }
+        self.sync_sections();
+    }
+
+    pub fn has_pill(&self, pill_id: PillId) -> bool {
+        self.pill_ids.contains(&pill_id)
+    }
+
+    /// Replaces the curriculum order. The new order must contain exactly the
+    /// pills already in the course, and keep each section's pills together in
+    /// section order, since pills move between sections only through
+    /// `move_pill_to_section`. Otherwise nothing changes and `false` is returned.
+    pub fn reorder_pills(&mut self, pill_ids: Vec<PillId>) -> bool {
+        if pill_ids.len() != self.pill_ids.len() {
+            return false;
+        }
+
+        let current: HashSet<&PillId> = self.pill_ids.iter().collect();
+        let requested: HashSet<&PillId> = pill_ids.iter().collect();
+        if requested.len() != pill_ids.len() || current != requested {
+            return false;
+        }
+
+        let section_orders: Vec<Vec<PillId>> = self
+            .sections
+            .iter()
+            .map(|section| {
+                let members: HashSet<&PillId> = section.pill_ids().iter().collect();
+                pill_ids
+                    .iter()
+                    .filter(|id| members.contains(id))
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        // An order that interleaves sections can't be stored as given.
+        if !section_orders.iter().flatten().eq(pill_ids.iter()) {
+            return false;
+        }
+
+        for (section, ordered) in self.sections.iter_mut().zip(section_orders) {
+            section.set_pill_ids(ordered);
+        }
+        self.sync_sections();
+        true
+    }
+
+    pub fn add_section(&mut self, section_id: SectionId, title: String) {
+        let position = self.sections.len();
+        self.sections
+            .push(Section::new(section_id, title, position));
+    }
+
+    pub fn rename_section(&mut self, section_id: SectionId, title: String) -> bool {
+        match self.sections.iter_mut().find(|s| s.id() == section_id) {
+            Some(section) => {
+                section.rename(title);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the section order. The new order must contain exactly the
+    /// current sections; otherwise nothing changes and `false` is returned.
+    pub fn reorder_sections(&mut self, section_ids: Vec<SectionId>) -> bool {
+        if section_ids.len() != self.sections.len() {
+            return false;
+        }
+
+        let mut reordered = Vec::with_capacity(self.sections.len());
+        for section_id in &section_ids {
+            match self.sections.iter().find(|s| s.id() == *section_id) {
+                Some(section) if !reordered.iter().any(|s: &Section| s.id() == *section_id) => {
+                    reordered.push(section.clone())
+                }
+                _ => return false,
+            }
+        }
+
+        self.sections = reordered;
+        self.sync_sections();
+        true
+    }
+
+    /// Removes an empty section. Sections that still hold pills are kept and
+    /// `false` is returned.
+    pub fn remove_section(&mut self, section_id: SectionId) -> bool {
+        match self.section(section_id) {
+            Some(section) if section.is_empty() => {
+                self.sections.retain(|s| s.id() != section_id);
+                self.sync_sections();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves a pill of the course into `section_id`, at `position` within the
+    /// section or at its end. Returns `false` if the pill or section is unknown.
+    pub fn move_pill(
+        &mut self,
+        pill_id: PillId,
+        section_id: SectionId,
+        position: Option<usize>,
+    ) -> bool {
+        if !self.has_pill(pill_id) || self.section(section_id).is_none() {
+            return false;
+        }
+
+        for section in &mut self.sections {
+            section.remove_pill(pill_id);
+        }
+
+        if let Some(section) = self.sections.iter_mut().find(|s| s.id() == section_id) {
+            match position {
+                Some(position) => section.insert_pill(position, pill_id),
+                None => section.push_pill(pill_id),
+            }
+        }
+        self.sync_sections();
+        true
     }
 
     pub fn pill_count(&self) -> usize {
         self.pill_ids.len()
     }
 
-    pub fn add_tag(&mut self, tag: String) {
-        if !self.tags.contains(&tag) {
-            self.tags.push(tag);
-        }
+    /// Replaces the tags with `tags`, keeping their order. Callers validate
+    /// them with [`Course::validate_tags`] first.
+    pub fn replace_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
     }
 
-    pub fn remove_tag(&mut self, tag: &str) {
-        self.tags.retain(|t| t != tag);
+    pub fn update_title(&mut self, title: String) {
+        self.title = title;
     }
 
-    pub fn has_tag(&self, tag: &str) -> bool {
-        self.tags.contains(&tag.to_string())
+    pub fn update_description(&mut self, description: String) {
+        self.description = description;
     }
 
     pub fn update_difficulty(&mut self, difficulty: Difficulty) {
@@ -206,4 +462,17 @@ impl Course {
     pub fn update_price(&mut self, price: f32) {
         self.price = price;
     }
+
+    /// Renumbers section positions and rebuilds the flattened pill list.
+    fn sync_sections(&mut self) {
+        for (position, section) in self.sections.iter_mut().enumerate() {
+            section.set_position(position);
+        }
+
+        self.pill_ids = self
+            .sections
+            .iter()
+            .flat_map(|section| section.pill_ids().iter().copied())
+            .collect();
+    }
 }
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
+use serde::Deserialize;
 use thiserror::Error;
 
+use crate::courses::domain::course::Difficulty;
 use crate::courses::domain::{Course, CourseId};
+use crate::pagination::{Cursor, Page, PageRequest, SortDirection};
+use crate::pills::domain::PillId;
+use crate::validation::ValidationErrors;
 
 #[derive(Error, Debug, Clone)]
 pub enum CourseRepositoryError {
@@ -11,12 +16,132 @@ pub enum CourseRepositoryError {
     NotFound,
     #[error("Course with this title already exists")]
     DuplicateTitle,
+    #[error("Referenced pills not found: {0:?}")]
+    PillsNotFound(Vec<PillId>),
+    #[error("Pill order must contain exactly the pills of the course, grouped by section")]
+    InvalidPillOrder,
+    #[error("Section not found")]
+    SectionNotFound,
+    #[error("Section still contains pills")]
+    SectionNotEmpty,
+    #[error("Section order must contain exactly the sections of the course")]
+    InvalidSectionOrder,
+    #[error("Invalid pagination cursor")]
+    InvalidCursor,
+    #[error("Validation failed: {0}")]
+    Invalid(#[from] ValidationErrors),
+    #[error("Course was modified by another request")]
+    ConcurrencyConflict,
+}
+
+/// Criteria for listing courses. Unset fields don't restrict the result.
+#[derive(Debug, Clone, Default)]
+pub struct CourseFilter {
+    pub instructor: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub tag: Option<String>,
+    pub min_price: Option<f32>,
+    pub max_price: Option<f32>,
+}
+
+impl CourseFilter {
+    /// Rejects a price range that no course can fall in.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let (Some(min_price), Some(max_price)) = (self.min_price, self.max_price) {
+            if min_price > max_price {
+                errors.add("min_price", "must not be greater than max_price");
+            }
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CourseSortField {
+    #[default]
+    Title,
+    Instructor,
+    Price,
+    Hours,
+}
+
+impl CourseSortField {
+    /// The name used in query strings and pagination cursors.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CourseSortField::Title => "title",
+            CourseSortField::Instructor => "instructor",
+            CourseSortField::Price => "price",
+            CourseSortField::Hours => "hours",
+        }
+    }
+
+    /// Value of this field for `course`, as stored in a pagination cursor.
+    pub fn value_of(&self, course: &Course) -> serde_json::Value {
+        match self {
+            CourseSortField::Title => course.title().into(),
+            CourseSortField::Instructor => course.instructor().into(),
+            CourseSortField::Price => f64::from(course.price()).into(),
+            CourseSortField::Hours => course.hours().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CourseSort {
+    pub field: CourseSortField,
+    pub direction: SortDirection,
+}
+
+impl CourseSort {
+    /// The cursor for the page that follows `course`.
+    pub fn cursor_after(&self, course: &Course) -> Cursor {
+        Cursor::new(
+            self.field.name(),
+            self.direction,
+            self.field.value_of(course),
+            course.id().to_string(),
+        )
+    }
 }
 
 #[async_trait]
 pub trait CourseRepository: Send + Sync {
-    async fn save(&self, course: &Course) -> Result<(), CourseRepositoryError>;
+    /// Stores the course if the stored copy is still at `course.version()`, or
+    /// doesn't exist yet for version 0, and advances the version on success.
+    /// Fails with `ConcurrencyConflict` otherwise.
+    async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError>;
+    /// Appends the pill to the course's last section, creating the default
+    /// section when there is none, as a single atomic update so concurrent
+    /// additions never overwrite each other. Adding a pill that is already part
+    /// of the course changes nothing. Returns the course's version afterwards.
+    async fn add_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError>;
+    /// Atomically removes the pill from the course and whichever section holds
+    /// it. Fails with `NotFound` if the course doesn't exist or doesn't contain
+    /// the pill. Returns the course's version afterwards.
+    async fn remove_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError>;
     async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError>;
-    async fn find_all(&self) -> Result<Vec<Course>, CourseRepositoryError>;
+    async fn find_page(
+        &self,
+        filter: &CourseFilter,
+        sort: CourseSort,
+        page: &PageRequest,
+    ) -> Result<Page<Course>, CourseRepositoryError>;
+    /// The number of stored courses. May be an estimate where an exact count is
+    /// expensive, so it's meant for monitoring rather than pagination.
+    async fn count(&self) -> Result<u64, CourseRepositoryError>;
     async fn find_by_title(&self, title: &str) -> Result<Option<Course>, CourseRepositoryError>;
+    async fn find_by_pill_id(&self, pill_id: PillId) -> Result<Vec<Course>, CourseRepositoryError>;
 }
@@ -1,6 +1,11 @@
 pub mod course;
 pub mod course_repository;
+pub mod section;
 
 pub use course::Course;
+pub use course::CourseDetails;
 pub use course::CourseId;
-pub use course_repository::{CourseRepository, CourseRepositoryError};
+pub use course_repository::{
+    CourseFilter, CourseRepository, CourseRepositoryError, CourseSort, CourseSortField,
+};
+pub use section::{Section, SectionId};
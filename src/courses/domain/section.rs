@@ -0,0 +1,138 @@
+use crate::pills::domain::PillId;
+use crate::validation::ValidationErrors;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SectionId(#[serde(with = "uuid_as_string")] Uuid);
+
+mod uuid_as_string {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use uuid::Uuid;
+
+    pub fn serialize<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        uuid.to_string().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Uuid::parse_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl SectionId {
+    pub fn new() -> Self {
+        SectionId(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for SectionId {
+    fn from(uuid: Uuid) -> Self {
+        SectionId(uuid)
+    }
+}
+
+impl FromStr for SectionId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = Uuid::parse_str(s)?;
+        Ok(SectionId(uuid))
+    }
+}
+
+impl Default for SectionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub const MAX_SECTION_TITLE_LENGTH: usize = 200;
+
+/// A chapter of a course: a titled, ordered group of pills.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    id: SectionId,
+    title: String,
+    position: usize,
+    pill_ids: Vec<PillId>,
+}
+
+impl Section {
+    pub fn create(id: SectionId, title: String, position: usize, pill_ids: Vec<PillId>) -> Self {
+        Self {
+            id,
+            title,
+            position,
+            pill_ids,
+        }
+    }
+
+    pub fn new(id: SectionId, title: String, position: usize) -> Self {
+        Self::create(id, title, position, Vec::new())
+    }
+
+    pub fn validate_title(title: &str, errors: &mut ValidationErrors) {
+        errors.check_text("title", title, MAX_SECTION_TITLE_LENGTH);
+    }
+
+    pub fn id(&self) -> SectionId {
+        self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn pill_ids(&self) -> &[PillId] {
+        &self.pill_ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pill_ids.is_empty()
+    }
+
+    pub(super) fn rename(&mut self, title: String) {
+        self.title = title;
+    }
+
+    pub(super) fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    pub(super) fn push_pill(&mut self, pill_id: PillId) {
+        self.pill_ids.push(pill_id);
+    }
+
+    pub(super) fn insert_pill(&mut self, position: usize, pill_id: PillId) {
+        let position = position.min(self.pill_ids.len());
+        self.pill_ids.insert(position, pill_id);
+    }
+
+    pub(super) fn remove_pill(&mut self, pill_id: PillId) {
+        self.pill_ids.retain(|id| *id != pill_id);
+    }
+
+    pub(super) fn set_pill_ids(&mut self, pill_ids: Vec<PillId>) {
+        self.pill_ids = pill_ids;
+    }
+}
@@ -1,11 +1,8 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
+use axum::{extract::State, response::Json};
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
 use crate::courses::application::{AddPillToCourseCommand, AddPillToCourseCommandHandler};
 use crate::courses::domain::{CourseId, CourseRepositoryError};
 use crate::pills::domain::PillId;
@@ -17,32 +14,24 @@ pub struct AddPillToCourseRequest {
 
 pub async fn add_pill_to_course_controller(
     State(handler): State<Arc<AddPillToCourseCommandHandler>>,
-    Path(course_id): Path<CourseId>,
-    Json(payload): Json<AddPillToCourseRequest>,
-) -> impl IntoResponse {
-    let command = AddPillToCourseCommand::new(course_id, payload.pill_id);
+    ApiPath(course_id): ApiPath<CourseId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<AddPillToCourseRequest>,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command = AddPillToCourseCommand::new(course_id, payload.pill_id)
+        .with_expected_version(expected_version);
 
-    match handler.handle(command).await {
-        Ok(_) => (
-            StatusCode::OK,
-            Json(serde_json::json!({
-                "message": "Pill added to course successfully"
-            })),
-        )
-            .into_response(),
-        Err(CourseRepositoryError::NotFound) => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": "Course or pill not found"
-            })),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-                "error": format!("Internal server error: {}", e),
-            })),
-        )
-            .into_response(),
-    }
+    let version = handler.handle(command).await.map_err(|e| match e {
+        CourseRepositoryError::NotFound => {
+            ApiError::not_found("course_or_pill_not_found", "Course or pill not found")
+        }
+        e => e.into(),
+    })?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Pill added to course successfully"
+        })),
+    ))
 }
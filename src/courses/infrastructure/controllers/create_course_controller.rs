@@ -1,12 +1,9 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
-use serde::{Deserialize, Serialize};
+use axum::extract::State;
+use serde::Deserialize;
 use std::sync::Arc;
 
-use crate::courses::domain::{CourseId, CourseRepositoryError};
+use crate::api::{ApiError, ApiJson, Created, ETag, Linked};
+use crate::courses::domain::Course;
 use crate::courses::{
     application::{CreateCourseCommand, CreateCourseCommandHandler},
     domain::course::Difficulty,
@@ -25,48 +22,25 @@ pub struct CreateCourseRequest {
     price: f32,
 }
 
-#[derive(Serialize)]
-pub struct CreateCourseResponse {
-    id: CourseId,
-    message: String,
-}
-
 pub async fn create_course_controller(
     State(handler): State<Arc<CreateCourseCommandHandler>>,
-    Json(payload): Json<CreateCourseRequest>,
-) -> impl IntoResponse {
-    let command = CreateCourseCommand::with_pills(
-        payload.title.clone(),
-        payload.description,
-        payload.instructor,
-        payload.pill_ids.unwrap_or_default(),
-        payload.difficulty,
-        payload.hours,
-        payload.tags,
-        payload.price,
-    );
+    ApiJson(payload): ApiJson<CreateCourseRequest>,
+) -> Result<(ETag, Created<Course>), ApiError> {
+    let command = CreateCourseCommand {
+        title: payload.title,
+        description: payload.description,
+        instructor: payload.instructor,
+        pill_ids: payload.pill_ids.unwrap_or_default(),
+        difficulty: payload.difficulty,
+        hours: payload.hours,
+        tags: payload.tags,
+        price: payload.price,
+    };
 
-    match handler.handle(command).await {
-        Ok(course_id) => {
-            let response = CreateCourseResponse {
-                id: course_id,
-                message: "Course created successfully".to_string(),
-            };
-            (StatusCode::CREATED, Json(response)).into_response()
-        }
-        Err(CourseRepositoryError::DuplicateTitle) => (
-            StatusCode::CONFLICT,
-            Json(serde_json::json!({
-                "error": "Course with this title already exists"
-            })),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-            "error": format!("Internal server error: {}", e),
-            })),
-        )
-            .into_response(),
-    }
+    let course = handler.handle(command).await?;
+    let href = format!("/courses/{}", course.id());
+    Ok((
+        ETag(course.version()),
+        Created(Linked::new(course, href.clone()).with_link("pills", format!("{}/pills", href))),
+    ))
 }
@@ -0,0 +1,30 @@
+use axum::extract::State;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, Created, ETag, IfMatch, Linked};
+use crate::courses::application::{CreateSectionCommand, CreateSectionCommandHandler};
+use crate::courses::domain::{CourseId, Section};
+
+#[derive(Deserialize)]
+pub struct CreateSectionRequest {
+    title: String,
+}
+
+pub async fn create_section_controller(
+    State(handler): State<Arc<CreateSectionCommandHandler>>,
+    ApiPath(course_id): ApiPath<CourseId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<CreateSectionRequest>,
+) -> Result<(ETag, Created<Section>), ApiError> {
+    let command =
+        CreateSectionCommand::new(course_id, payload.title).with_expected_version(expected_version);
+
+    let (section, version) = handler.handle(command).await?;
+    let course_href = format!("/courses/{}", course_id);
+    let href = format!("{}/sections/{}", course_href, section.id());
+    Ok((
+        ETag(version),
+        Created(Linked::new(section, href).with_link("course", course_href)),
+    ))
+}
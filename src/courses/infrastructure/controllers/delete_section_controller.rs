@@ -0,0 +1,18 @@
+use axum::{extract::State, http::StatusCode};
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiPath, ETag, IfMatch};
+use crate::courses::application::{DeleteSectionCommand, DeleteSectionCommandHandler};
+use crate::courses::domain::{CourseId, SectionId};
+
+pub async fn delete_section_controller(
+    State(handler): State<Arc<DeleteSectionCommandHandler>>,
+    ApiPath((course_id, section_id)): ApiPath<(CourseId, SectionId)>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<(ETag, StatusCode), ApiError> {
+    let command =
+        DeleteSectionCommand::new(course_id, section_id).with_expected_version(expected_version);
+
+    let version = handler.handle(command).await?;
+    Ok((ETag(version), StatusCode::NO_CONTENT))
+}
@@ -1,25 +1,60 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
 use std::sync::Arc;
 
-use crate::courses::application::{FindAllCoursesQuery, FindAllCoursesQueryHandler};
+use crate::api::{ApiError, ApiQuery};
+use crate::courses::application::{
+    CourseRepositoryError, FindAllCoursesQuery, FindAllCoursesQueryHandler,
+};
+use crate::courses::domain::course::Difficulty;
+use crate::courses::domain::{Course, CourseFilter, CourseSort, CourseSortField};
+use crate::pagination::{Cursor, Page, PageRequest, SortDirection};
+
+#[derive(Deserialize)]
+pub struct FindAllCoursesParams {
+    instructor: Option<String>,
+    difficulty: Option<Difficulty>,
+    tag: Option<String>,
+    min_price: Option<f32>,
+    max_price: Option<f32>,
+    sort: Option<CourseSortField>,
+    order: Option<SortDirection>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
 
 pub async fn find_all_courses_constroller(
     State(handler): State<Arc<FindAllCoursesQueryHandler>>,
-) -> impl IntoResponse {
-    let query = FindAllCoursesQuery;
+    ApiQuery(params): ApiQuery<FindAllCoursesParams>,
+) -> Result<Json<Page<Course>>, ApiError> {
+    let sort = CourseSort {
+        field: params.sort.unwrap_or_default(),
+        direction: params.order.unwrap_or_default(),
+    };
+
+    // A cursor only continues the sort it was issued for.
+    let cursor = match params.cursor.as_deref() {
+        Some(encoded) => match Cursor::decode_for(encoded, sort.field.name(), sort.direction) {
+            Some(cursor) => Some(cursor),
+            None => return Err(CourseRepositoryError::InvalidCursor.into()),
+        },
+        None => None,
+    };
+
+    let filter = CourseFilter {
+        instructor: params.instructor,
+        difficulty: params.difficulty,
+        tag: params.tag,
+        min_price: params.min_price,
+        max_price: params.max_price,
+    };
+    filter.validate()?;
+
+    let query = FindAllCoursesQuery {
+        filter,
+        sort,
+        page: PageRequest::new(params.limit, cursor)?,
+    };
 
-    match handler.handle(query).await {
-        Ok(courses) => (StatusCode::OK, Json(courses)).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-                "error": format!("Internal server error: {}", e),
-            })),
-        )
-            .into_response(),
-    }
+    Ok(Json(handler.handle(query).await?))
 }
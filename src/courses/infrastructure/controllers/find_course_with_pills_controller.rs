@@ -1,36 +1,22 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::State,
     response::{IntoResponse, Json},
 };
 use std::sync::Arc;
 
-use crate::courses::application::{
-    CourseRepositoryError, FindCourseWithPillsQuery, FindCourseWithPillsQueryHandler,
-};
+use crate::api::{ApiError, ApiPath, ETag};
+use crate::courses::application::{FindCourseWithPillsQuery, FindCourseWithPillsQueryHandler};
 use crate::courses::domain::CourseId;
 
 pub async fn find_course_with_pills_controller(
     State(handler): State<Arc<FindCourseWithPillsQueryHandler>>,
-    Path(id): Path<CourseId>,
-) -> impl IntoResponse {
+    ApiPath(id): ApiPath<CourseId>,
+) -> Result<impl IntoResponse, ApiError> {
     let query = FindCourseWithPillsQuery { id };
 
-    match handler.handle(query).await {
-        Ok(course_with_pills) => (StatusCode::OK, Json(course_with_pills)).into_response(),
-        Err(CourseRepositoryError::NotFound) => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": "Course not found"
-            })),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({
-                "error": format!("Internal server error: {}", e),
-            })),
-        )
-            .into_response(),
-    }
+    let course_with_pills = handler.handle(query).await?;
+    Ok((
+        ETag(course_with_pills.course.version()),
+        Json(course_with_pills),
+    ))
 }
@@ -0,0 +1,26 @@
+use axum::{extract::State, response::Json};
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiPath, ETag, Linked};
+use crate::courses::application::{CourseRepositoryError, FindCourseQuery, FindCourseQueryHandler};
+use crate::courses::domain::{CourseId, Section, SectionId};
+
+/// Serves the `Location` of a created section. The `ETag` is the course's version,
+/// as sections are changed through their course.
+pub async fn find_section_controller(
+    State(handler): State<Arc<FindCourseQueryHandler>>,
+    ApiPath((course_id, section_id)): ApiPath<(CourseId, SectionId)>,
+) -> Result<(ETag, Json<Linked<Section>>), ApiError> {
+    let course = handler.handle(FindCourseQuery { id: course_id }).await?;
+    let section = course
+        .section(section_id)
+        .cloned()
+        .ok_or(CourseRepositoryError::SectionNotFound)?;
+
+    let course_href = format!("/courses/{}", course_id);
+    let href = format!("{}/sections/{}", course_href, section_id);
+    Ok((
+        ETag(course.version()),
+        Json(Linked::new(section, href).with_link("course", course_href)),
+    ))
+}
@@ -1,5 +1,14 @@
 pub mod add_pill_to_course_controller;
 pub mod create_course_controller;
+pub mod create_section_controller;
+pub mod delete_section_controller;
 pub mod find_all_courses_controller;
 pub mod find_course_controller;
 pub mod find_course_with_pills_controller;
+pub mod find_section_controller;
+pub mod move_pill_to_section_controller;
+pub mod remove_pill_from_course_controller;
+pub mod rename_section_controller;
+pub mod reorder_course_pills_controller;
+pub mod reorder_sections_controller;
+pub mod update_course_controller;
@@ -0,0 +1,47 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::courses::application::{MovePillToSectionCommand, MovePillToSectionCommandHandler};
+use crate::courses::domain::{CourseId, CourseRepositoryError, SectionId};
+use crate::pills::domain::PillId;
+
+#[derive(Deserialize)]
+pub struct MovePillToSectionRequest {
+    section_id: SectionId,
+    position: Option<usize>,
+}
+
+pub async fn move_pill_to_section_controller(
+    State(handler): State<Arc<MovePillToSectionCommandHandler>>,
+    ApiPath((course_id, pill_id)): ApiPath<(CourseId, PillId)>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<MovePillToSectionRequest>,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command =
+        MovePillToSectionCommand::new(course_id, pill_id, payload.section_id, payload.position)
+            .with_expected_version(expected_version);
+
+    let version = handler
+        .handle(command)
+        .await
+        .map_err(course_or_pill_not_found)?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Pill moved successfully"
+        })),
+    ))
+}
+
+pub(super) fn course_or_pill_not_found(error: CourseRepositoryError) -> ApiError {
+    match error {
+        CourseRepositoryError::NotFound => ApiError::not_found(
+            "course_or_pill_not_found",
+            "Course not found or pill not part of the course",
+        ),
+        e => e.into(),
+    }
+}
@@ -0,0 +1,31 @@
+use axum::{extract::State, response::Json};
+use std::sync::Arc;
+
+use super::move_pill_to_section_controller::course_or_pill_not_found;
+use crate::api::{ApiError, ApiPath, ETag, IfMatch};
+use crate::courses::application::{
+    RemovePillFromCourseCommand, RemovePillFromCourseCommandHandler,
+};
+use crate::courses::domain::CourseId;
+use crate::pills::domain::PillId;
+
+pub async fn remove_pill_from_course_controller(
+    State(handler): State<Arc<RemovePillFromCourseCommandHandler>>,
+    ApiPath((course_id, pill_id)): ApiPath<(CourseId, PillId)>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command = RemovePillFromCourseCommand::new(course_id, pill_id)
+        .with_expected_version(expected_version);
+
+    let version = handler
+        .handle(command)
+        .await
+        .map_err(course_or_pill_not_found)?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Pill removed from course successfully"
+        })),
+    ))
+}
@@ -0,0 +1,31 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::courses::application::{RenameSectionCommand, RenameSectionCommandHandler};
+use crate::courses::domain::{CourseId, SectionId};
+
+#[derive(Deserialize)]
+pub struct RenameSectionRequest {
+    title: String,
+}
+
+pub async fn rename_section_controller(
+    State(handler): State<Arc<RenameSectionCommandHandler>>,
+    ApiPath((course_id, section_id)): ApiPath<(CourseId, SectionId)>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<RenameSectionRequest>,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command = RenameSectionCommand::new(course_id, section_id, payload.title)
+        .with_expected_version(expected_version);
+
+    let version = handler.handle(command).await?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Section renamed successfully"
+        })),
+    ))
+}
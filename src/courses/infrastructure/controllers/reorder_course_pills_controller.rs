@@ -0,0 +1,32 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::courses::application::{ReorderCoursePillsCommand, ReorderCoursePillsCommandHandler};
+use crate::courses::domain::CourseId;
+use crate::pills::domain::PillId;
+
+#[derive(Deserialize)]
+pub struct ReorderCoursePillsRequest {
+    pill_ids: Vec<PillId>,
+}
+
+pub async fn reorder_course_pills_controller(
+    State(handler): State<Arc<ReorderCoursePillsCommandHandler>>,
+    ApiPath(course_id): ApiPath<CourseId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<ReorderCoursePillsRequest>,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command = ReorderCoursePillsCommand::new(course_id, payload.pill_ids)
+        .with_expected_version(expected_version);
+
+    let version = handler.handle(command).await?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Course pills reordered successfully"
+        })),
+    ))
+}
@@ -0,0 +1,31 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::courses::application::{ReorderSectionsCommand, ReorderSectionsCommandHandler};
+use crate::courses::domain::{CourseId, SectionId};
+
+#[derive(Deserialize)]
+pub struct ReorderSectionsRequest {
+    section_ids: Vec<SectionId>,
+}
+
+pub async fn reorder_sections_controller(
+    State(handler): State<Arc<ReorderSectionsCommandHandler>>,
+    ApiPath(course_id): ApiPath<CourseId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<ReorderSectionsRequest>,
+) -> Result<(ETag, Json<serde_json::Value>), ApiError> {
+    let command = ReorderSectionsCommand::new(course_id, payload.section_ids)
+        .with_expected_version(expected_version);
+
+    let version = handler.handle(command).await?;
+
+    Ok((
+        ETag(version),
+        Json(serde_json::json!({
+            "message": "Sections reordered successfully"
+        })),
+    ))
+}
@@ -0,0 +1,40 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::courses::application::{UpdateCourseCommand, UpdateCourseCommandHandler};
+use crate::courses::domain::{course::Difficulty, Course, CourseId};
+
+/// JSON merge patch body. Omitted (or `null`) fields are left unchanged and
+/// `tags`, when present, replaces the whole tag list.
+#[derive(Deserialize)]
+pub struct UpdateCourseRequest {
+    title: Option<String>,
+    description: Option<String>,
+    difficulty: Option<Difficulty>,
+    hours: Option<i8>,
+    tags: Option<Vec<String>>,
+    price: Option<f32>,
+}
+
+pub async fn update_course_controller(
+    State(handler): State<Arc<UpdateCourseCommandHandler>>,
+    ApiPath(course_id): ApiPath<CourseId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<UpdateCourseRequest>,
+) -> Result<(ETag, Json<Course>), ApiError> {
+    let command = UpdateCourseCommand {
+        course_id,
+        title: payload.title,
+        description: payload.description,
+        difficulty: payload.difficulty,
+        hours: payload.hours,
+        tags: payload.tags,
+        price: payload.price,
+        expected_version,
+    };
+
+    let course = handler.handle(command).await?;
+    Ok((ETag(course.version()), Json(course)))
+}
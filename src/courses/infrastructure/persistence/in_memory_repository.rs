@@ -0,0 +1,237 @@
+use async_trait::async_trait;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::courses::domain::{
+    Course, CourseFilter, CourseId, CourseRepository, CourseRepositoryError, CourseSort,
+};
+use crate::pagination::{paginate, Page, PageRequest};
+use crate::pills::domain::PillId;
+
+/// Process-local course store with the same semantics as `MongoDbCourseRepository`:
+/// `save` upserts by ID, courses keep their insertion order and title lookup is
+/// an exact, case-sensitive match. Data is lost on restart.
+#[derive(Default)]
+pub struct InMemoryCourseRepository {
+    courses: RwLock<Vec<Course>>,
+}
+
+impl InMemoryCourseRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of every stored course, in insertion order.
+    pub fn all(&self) -> Result<Vec<Course>, CourseRepositoryError> {
+        Ok(self.read()?.clone())
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<'_, Vec<Course>>, CourseRepositoryError> {
+        self.courses.read().map_err(|_| {
+            tracing::error!("Repository: In-memory course store is poisoned");
+            CourseRepositoryError::Unexpected
+        })
+    }
+
+    fn write(&self) -> Result<RwLockWriteGuard<'_, Vec<Course>>, CourseRepositoryError> {
+        self.courses.write().map_err(|_| {
+            tracing::error!("Repository: In-memory course store is poisoned");
+            CourseRepositoryError::Unexpected
+        })
+    }
+
+    /// Applies `change` to a stored course under the write lock, so it can't
+    /// interleave with any other write. `change` reports whether it modified
+    /// the course; only then is the version advanced.
+    fn modify(
+        &self,
+        course_id: CourseId,
+        expected_version: Option<u64>,
+        change: impl FnOnce(&mut Course) -> Result<bool, CourseRepositoryError>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let mut courses = self.write()?;
+        let course = courses
+            .iter_mut()
+            .find(|c| c.id() == course_id)
+            .ok_or(CourseRepositoryError::NotFound)?;
+
+        if !course.matches_version(expected_version) {
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        if change(course)? {
+            course.set_version(course.version() + 1);
+        }
+        Ok(course.version())
+    }
+}
+
+fn matches(filter: &CourseFilter, course: &Course) -> bool {
+    // Prices are compared as stored in MongoDB, widened to f64.
+    let price = f64::from(course.price());
+
+    filter
+        .instructor
+        .as_ref()
+        .is_none_or(|instructor| course.instructor() == instructor)
+        && filter
+            .difficulty
+            .is_none_or(|difficulty| course.difficulty() == difficulty)
+        && filter
+            .tag
+            .as_ref()
+            .is_none_or(|tag| course.tags().contains(tag))
+        && filter
+            .min_price
+            .is_none_or(|min_price| price >= f64::from(min_price))
+        && filter
+            .max_price
+            .is_none_or(|max_price| price <= f64::from(max_price))
+}
+
+#[async_trait]
+impl CourseRepository for InMemoryCourseRepository {
+    async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError> {
+        let mut courses = self.write()?;
+        let expected = course.version();
+        let existing = courses.iter().position(|c| c.id() == course.id());
+
+        let stored_version = existing.map_or(0, |index| courses[index].version());
+        if stored_version != expected {
+            tracing::warn!(
+                "Repository: Course {} is no longer at version {}",
+                course.id(),
+                expected
+            );
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+
+        course.set_version(expected + 1);
+        match existing {
+            Some(index) => courses[index] = course.clone(),
+            None => courses.push(course.clone()),
+        }
+
+        tracing::info!(
+            "Repository: Course {} saved in memory at version {}",
+            course.id(),
+            course.version()
+        );
+        Ok(())
+    }
+
+    async fn add_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let version = self.modify(course_id, expected_version, |course| {
+            if course.has_pill(pill_id) {
+                return Ok(false);
+            }
+            course.add_pill(pill_id);
+            Ok(true)
+        })?;
+
+        tracing::info!(
+            "Repository: Pill {} added to course {} in memory",
+            pill_id,
+            course_id
+        );
+        Ok(version)
+    }
+
+    async fn remove_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let version = self.modify(course_id, expected_version, |course| {
+            if !course.has_pill(pill_id) {
+                return Err(CourseRepositoryError::NotFound);
+            }
+            course.remove_pill(pill_id);
+            Ok(true)
+        })?;
+
+        tracing::info!(
+            "Repository: Pill {} removed from course {} in memory",
+            pill_id,
+            course_id
+        );
+        Ok(version)
+    }
+
+    async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError> {
+        let course = self.read()?.iter().find(|c| c.id() == id).cloned();
+
+        match &course {
+            Some(_) => tracing::debug!("Repository: Found course {}", id),
+            None => tracing::debug!("Repository: Course {} not found", id),
+        }
+
+        Ok(course)
+    }
+
+    async fn find_page(
+        &self,
+        filter: &CourseFilter,
+        sort: CourseSort,
+        page: &PageRequest,
+    ) -> Result<Page<Course>, CourseRepositoryError> {
+        let courses: Vec<Course> = self
+            .read()?
+            .iter()
+            .filter(|course| matches(filter, course))
+            .cloned()
+            .collect();
+
+        let page = paginate(
+            courses,
+            sort.field.name(),
+            sort.direction,
+            page,
+            |course| sort.field.value_of(course),
+            |course| course.id().to_string(),
+        );
+
+        tracing::info!(
+            "Repository: Found {} courses (page of {} total)",
+            page.items.len(),
+            page.total
+        );
+        Ok(page)
+    }
+
+    async fn count(&self) -> Result<u64, CourseRepositoryError> {
+        Ok(self.read()?.len() as u64)
+    }
+
+    async fn find_by_title(&self, title: &str) -> Result<Option<Course>, CourseRepositoryError> {
+        let course = self.read()?.iter().find(|c| c.title() == title).cloned();
+
+        match &course {
+            Some(c) => tracing::debug!("Repository: Found course '{}' by title", c.title()),
+            None => tracing::debug!("Repository: Course with title '{}' not found", title),
+        }
+
+        Ok(course)
+    }
+
+    async fn find_by_pill_id(&self, pill_id: PillId) -> Result<Vec<Course>, CourseRepositoryError> {
+        let courses: Vec<Course> = self
+            .read()?
+            .iter()
+            .filter(|course| course.has_pill(pill_id))
+            .cloned()
+            .collect();
+
+        tracing::debug!(
+            "Repository: Found {} courses referencing pill {}",
+            courses.len(),
+            pill_id
+        );
+        Ok(courses)
+    }
+}
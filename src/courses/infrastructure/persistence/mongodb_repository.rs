@@ -1,10 +1,23 @@
 use async_trait::async_trait;
-use futures::TryStreamExt;
-use mongodb::{bson::doc, Collection, Database};
+use mongodb::{
+    bson::{doc, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Database,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-use crate::courses::domain::{Course, CourseId, CourseRepository, CourseRepositoryError};
 use crate::courses::domain::course::Difficulty;
+use crate::courses::domain::{
+    Course, CourseDetails, CourseId, CourseRepository, CourseRepositoryError,
+};
+use crate::courses::domain::{CourseFilter, CourseSort, CourseSortField, Section, SectionId};
+use crate::database::error::map_mongodb_error;
+use crate::database::pagination::{after_cursor, sort_document};
+use crate::database::session::{MongoSession, SessionCollection};
+use crate::database::versioning::version_filter;
+use crate::database::DatabaseError;
+use crate::pagination::{Page, PageRequest};
 use crate::pills::domain::PillId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +32,44 @@ struct CourseDocument {
     tags: Vec<String>,
     price: f32,
     pill_ids: Vec<String>,
+    // Courses stored before sections existed have no `sections` field; their
+    // flat `pill_ids` are exposed as a single default section.
+    #[serde(default)]
+    sections: Vec<SectionDocument>,
+    // Courses stored before versioning have no `version` field and start at 0.
+    #[serde(default)]
+    version: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SectionDocument {
+    id: String,
+    title: String,
+    position: usize,
+    pill_ids: Vec<String>,
+}
+
+impl From<&Section> for SectionDocument {
+    fn from(section: &Section) -> Self {
+        Self {
+            id: section.id().to_string(),
+            title: section.title().to_string(),
+            position: section.position(),
+            pill_ids: section.pill_ids().iter().map(|id| id.to_string()).collect(),
+        }
+    }
+}
+
+impl TryFrom<SectionDocument> for Section {
+    type Error = uuid::Error;
+
+    fn try_from(doc: SectionDocument) -> Result<Self, Self::Error> {
+        let id = doc.id.parse::<SectionId>()?;
+        let pill_ids: Result<Vec<PillId>, _> =
+            doc.pill_ids.iter().map(|s| s.parse::<PillId>()).collect();
+
+        Ok(Section::create(id, doc.title, doc.position, pill_ids?))
+    }
 }
 
 impl From<&Course> for CourseDocument {
@@ -33,6 +84,12 @@ impl From<&Course> for CourseDocument {
             tags: course.tags().to_vec(),
             price: course.price(),
             pill_ids: course.pill_ids().iter().map(|id| id.to_string()).collect(),
+            sections: course
+                .sections()
+                .iter()
+                .map(SectionDocument::from)
+                .collect(),
+            version: course.version() as i64,
         }
     }
 }
@@ -42,42 +99,155 @@ impl TryFrom<CourseDocument> for Course {
 
     fn try_from(doc: CourseDocument) -> Result<Self, Self::Error> {
         let id = doc.id.parse::<CourseId>()?;
-        let pill_ids: Result<Vec<PillId>, _> = doc.pill_ids
-            .iter()
-            .map(|s| s.parse::<PillId>())
-            .collect();
+        let pill_ids: Result<Vec<PillId>, _> =
+            doc.pill_ids.iter().map(|s| s.parse::<PillId>()).collect();
         let pill_ids = pill_ids?;
 
-        Ok(Course::create(
-            id,
-            doc.title,
-            doc.description,
-            doc.instructor,
-            doc.difficulty,
-            doc.hours,
-            doc.tags,
-            doc.price,
-            pill_ids,
-        ))
+        let sections = if doc.sections.is_empty() && !pill_ids.is_empty() {
+            vec![Course::default_section(id, pill_ids)]
+        } else {
+            let sections: Result<Vec<Section>, _> =
+                doc.sections.into_iter().map(Section::try_from).collect();
+            sections?
+        };
+
+        let details = CourseDetails {
+            title: doc.title,
+            description: doc.description,
+            instructor: doc.instructor,
+            difficulty: doc.difficulty,
+            hours: doc.hours,
+            tags: doc.tags,
+            price: doc.price,
+        };
+
+        Ok(Course::create(id, details, sections).with_version(doc.version as u64))
+    }
+}
+
+fn filter_document(filter: &CourseFilter) -> Document {
+    let mut query = Document::new();
+
+    if let Some(instructor) = &filter.instructor {
+        query.insert("instructor", instructor);
+    }
+
+    if let Some(difficulty) = filter.difficulty {
+        query.insert("difficulty", difficulty.to_string());
+    }
+
+    if let Some(tag) = &filter.tag {
+        query.insert("tags", tag);
+    }
+
+    let mut price = Document::new();
+    if let Some(min_price) = filter.min_price {
+        price.insert("$gte", f64::from(min_price));
+    }
+    if let Some(max_price) = filter.max_price {
+        price.insert("$lte", f64::from(max_price));
+    }
+    if !price.is_empty() {
+        query.insert("price", price);
+    }
+
+    query
+}
+
+fn sort_key(field: CourseSortField) -> &'static str {
+    match field {
+        CourseSortField::Title => "title",
+        CourseSortField::Instructor => "instructor",
+        CourseSortField::Price => "price",
+        CourseSortField::Hours => "hours",
+    }
+}
+
+/// Attempts at an atomic membership update before giving up. A retry is only
+/// needed when the course's sections change between reading and updating it.
+const MEMBERSHIP_ATTEMPTS: usize = 5;
+
+fn membership_filter(course_id: CourseId, expected_version: Option<u64>) -> Document {
+    match expected_version {
+        Some(expected) => version_filter(course_id.to_string(), expected),
+        None => doc! { "_id": course_id.to_string() },
     }
 }
 
 pub struct MongoDbCourseRepository {
-    collection: Collection<CourseDocument>,
+    collection: SessionCollection<CourseDocument>,
 }
 
 impl MongoDbCourseRepository {
     pub fn new(database: &Database) -> Self {
+        Self::with_optional_session(database, None)
+    }
+
+    /// A repository whose reads and writes all belong to `session`'s transaction.
+    pub fn with_session(database: &Database, session: Arc<MongoSession>) -> Self {
+        Self::with_optional_session(database, Some(session))
+    }
+
+    fn with_optional_session(database: &Database, session: Option<Arc<MongoSession>>) -> Self {
         let collection = database.collection::<CourseDocument>("courses");
-        Self { collection }
+        Self {
+            collection: SessionCollection::new(collection, session),
+        }
+    }
+
+    async fn find_document(
+        &self,
+        id: CourseId,
+    ) -> Result<Option<CourseDocument>, CourseRepositoryError> {
+        let filter = doc! { "_id": id.to_string() };
+
+        self.collection.find_one(filter, None).await.map_err(|e| {
+            tracing::error!("Repository: Failed to find course {}: {}", id, e);
+            CourseRepositoryError::Unexpected
+        })
+    }
+
+    /// Applies `update` to the course matching `filter` and returns the version
+    /// it ends up at, or `None` if no course matched.
+    async fn update_membership(
+        &self,
+        course_id: CourseId,
+        filter: Document,
+        update: Document,
+        array_filters: Option<Vec<Document>>,
+    ) -> Result<Option<u64>, CourseRepositoryError> {
+        let options = FindOneAndUpdateOptions::builder()
+            .array_filters(array_filters)
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let updated = self
+            .collection
+            .find_one_and_update(filter, update, options)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "Repository: Failed to update pills of course {}: {}",
+                    course_id,
+                    e
+                );
+                CourseRepositoryError::Unexpected
+            })?;
+
+        Ok(updated.map(|doc| doc.version as u64))
     }
 }
 
 #[async_trait]
 impl CourseRepository for MongoDbCourseRepository {
-    async fn save(&self, course: &Course) -> Result<(), CourseRepositoryError> {
-        let course_doc = CourseDocument::from(course);
-        let filter = doc! { "_id": course.id().to_string() };
+    async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError> {
+        let expected = course.version();
+        let mut course_doc = CourseDocument::from(&*course);
+        course_doc.version = (expected + 1) as i64;
+
+        // Only a new course may be inserted; if another writer got there first the
+        // insert hits the `_id` unique index instead.
+        let filter = version_filter(course.id().to_string(), expected);
         let update = doc! {
             "$set": mongodb::bson::to_document(&course_doc)
                 .map_err(|e| {
@@ -92,18 +262,32 @@ impl CourseRepository for MongoDbCourseRepository {
                 filter,
                 update,
                 mongodb::options::UpdateOptions::builder()
-                    .upsert(true)
+                    .upsert(expected == 0)
                     .build(),
             )
             .await
-            .map_err(|e| {
-                tracing::error!("Repository: Failed to save course {}: {}", course.id(), e);
-                CourseRepositoryError::Unexpected
+            .map_err(|e| match map_mongodb_error(e) {
+                DatabaseError::DuplicateKey => CourseRepositoryError::ConcurrencyConflict,
+                e => {
+                    tracing::error!("Repository: Failed to save course {}: {}", course.id(), e);
+                    CourseRepositoryError::Unexpected
+                }
             })?;
 
+        if result.matched_count == 0 && result.upserted_id.is_none() {
+            tracing::warn!(
+                "Repository: Course {} is no longer at version {}",
+                course.id(),
+                expected
+            );
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+        course.set_version(expected + 1);
+
         tracing::info!(
-            "Repository: Course {} saved successfully. Modified: {}, Matched: {}",
+            "Repository: Course {} saved at version {}. Modified: {}, Matched: {}",
             course.id(),
+            course.version(),
             result.modified_count,
             result.matched_count
         );
@@ -111,6 +295,145 @@ impl CourseRepository for MongoDbCourseRepository {
         Ok(())
     }
 
+    async fn add_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let pill = pill_id.to_string();
+
+        for _ in 0..MEMBERSHIP_ATTEMPTS {
+            let course = self
+                .find_document(course_id)
+                .await?
+                .ok_or(CourseRepositoryError::NotFound)?;
+
+            if expected_version.is_some_and(|expected| expected != course.version as u64) {
+                return Err(CourseRepositoryError::ConcurrencyConflict);
+            }
+            if course.pill_ids.contains(&pill) {
+                return Ok(course.version as u64);
+            }
+
+            let mut filter = membership_filter(course_id, expected_version);
+            let updated = match course.sections.last() {
+                // Sections are stored in position order. The last one is pinned by
+                // index and ID so a concurrent section change makes the update miss.
+                Some(last) => {
+                    let last_index = course.sections.len() - 1;
+                    filter.insert(format!("sections.{}.id", last_index), &last.id);
+                    filter.insert("pill_ids", doc! { "$ne": &pill });
+                    let update = doc! {
+                        "$addToSet": { "pill_ids": &pill, "sections.$[last].pill_ids": &pill },
+                        "$inc": { "version": 1_i64 },
+                    };
+                    let array_filters = vec![doc! { "last.id": &last.id }];
+                    self.update_membership(course_id, filter, update, Some(array_filters))
+                        .await?
+                }
+                // Without stored sections the pills go into the default section,
+                // matching the current pill list exactly so none can be lost.
+                None => {
+                    let mut pill_ids = course.pill_ids.clone();
+                    pill_ids.push(pill.clone());
+                    let mut section =
+                        SectionDocument::from(&Course::default_section(course_id, Vec::new()));
+                    section.pill_ids = pill_ids.clone();
+                    let section = mongodb::bson::to_document(&section).map_err(|e| {
+                        tracing::error!(
+                            "Repository: Failed to serialize default section of course {}: {}",
+                            course_id,
+                            e
+                        );
+                        CourseRepositoryError::Unexpected
+                    })?;
+
+                    filter.insert("pill_ids", &course.pill_ids);
+                    filter.insert("sections.0", doc! { "$exists": false });
+                    let update = doc! {
+                        "$set": { "sections": [section], "pill_ids": pill_ids },
+                        "$inc": { "version": 1_i64 },
+                    };
+                    self.update_membership(course_id, filter, update, None)
+                        .await?
+                }
+            };
+
+            if let Some(version) = updated {
+                tracing::info!("Repository: Pill {} added to course {}", pill_id, course_id);
+                return Ok(version);
+            }
+        }
+
+        tracing::warn!(
+            "Repository: Gave up adding pill {} to course {} after concurrent changes",
+            pill_id,
+            course_id
+        );
+        Err(CourseRepositoryError::ConcurrencyConflict)
+    }
+
+    async fn remove_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let pill = pill_id.to_string();
+
+        for _ in 0..MEMBERSHIP_ATTEMPTS {
+            let course = self
+                .find_document(course_id)
+                .await?
+                .ok_or(CourseRepositoryError::NotFound)?;
+
+            if expected_version.is_some_and(|expected| expected != course.version as u64) {
+                return Err(CourseRepositoryError::ConcurrencyConflict);
+            }
+            if !course.pill_ids.contains(&pill) {
+                return Err(CourseRepositoryError::NotFound);
+            }
+
+            let mut filter = membership_filter(course_id, expected_version);
+            filter.insert("pill_ids", &pill);
+            // `$[]` fails on courses stored before sections existed, which have
+            // no `sections` array, so those only lose the pill from `pill_ids`.
+            let update = if course.sections.is_empty() {
+                filter.insert("sections.0", doc! { "$exists": false });
+                doc! {
+                    "$pull": { "pill_ids": &pill },
+                    "$inc": { "version": 1_i64 },
+                }
+            } else {
+                filter.insert("sections.0", doc! { "$exists": true });
+                doc! {
+                    "$pull": { "pill_ids": &pill, "sections.$[].pill_ids": &pill },
+                    "$inc": { "version": 1_i64 },
+                }
+            };
+
+            if let Some(version) = self
+                .update_membership(course_id, filter, update, None)
+                .await?
+            {
+                tracing::info!(
+                    "Repository: Pill {} removed from course {}",
+                    pill_id,
+                    course_id
+                );
+                return Ok(version);
+            }
+        }
+
+        tracing::warn!(
+            "Repository: Gave up removing pill {} from course {} after concurrent changes",
+            pill_id,
+            course_id
+        );
+        Err(CourseRepositoryError::ConcurrencyConflict)
+    }
+
     async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError> {
         let filter = doc! { "_id": id.to_string() };
 
@@ -135,32 +458,75 @@ impl CourseRepository for MongoDbCourseRepository {
         Ok(course)
     }
 
-    async fn find_all(&self) -> Result<Vec<Course>, CourseRepositoryError> {
-        let cursor = self.collection.find(doc! {}, None).await.map_err(|e| {
-            tracing::error!(
-                "Repository: Failed to create cursor for find_all courses: {}",
-                e
-            );
-            CourseRepositoryError::Unexpected
-        })?;
+    async fn find_page(
+        &self,
+        filter: &CourseFilter,
+        sort: CourseSort,
+        page: &PageRequest,
+    ) -> Result<Page<Course>, CourseRepositoryError> {
+        let query = filter_document(filter);
+        let sort_key = sort_key(sort.field);
 
-        let course_docs: Vec<CourseDocument> = cursor.try_collect().await.map_err(|e| {
-            tracing::error!("Repository: Failed to collect courses from cursor: {}", e);
+        let total = self
+            .collection
+            .count_documents(query.clone(), None)
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to count courses: {}", e);
+                CourseRepositoryError::Unexpected
+            })?;
+
+        let query = match &page.cursor {
+            Some(cursor) => after_cursor(query, sort_key, sort.direction, cursor)
+                .ok_or(CourseRepositoryError::InvalidCursor)?,
+            None => query,
+        };
+
+        let options = FindOptions::builder()
+            .sort(sort_document(sort_key, sort.direction))
+            .limit(i64::from(page.limit) + 1)
+            .build();
+
+        let course_docs = self.collection.find(query, options).await.map_err(|e| {
+            tracing::error!("Repository: Failed to find courses page: {}", e);
             CourseRepositoryError::Unexpected
         })?;
 
-        let courses: Result<Vec<Course>, _> = course_docs
-            .into_iter()
-            .map(|doc| doc.try_into())
-            .collect();
+        let courses: Result<Vec<Course>, _> =
+            course_docs.into_iter().map(|doc| doc.try_into()).collect();
 
-        let courses = courses.map_err(|e| {
+        let mut courses = courses.map_err(|e| {
             tracing::error!("Repository: Failed to deserialize courses: {}", e);
             CourseRepositoryError::Unexpected
         })?;
 
-        tracing::info!("Repository: Found {} courses", courses.len());
-        Ok(courses)
+        let next_cursor = if courses.len() > page.limit as usize {
+            courses.truncate(page.limit as usize);
+            courses.last().map(|c| sort.cursor_after(c).encode())
+        } else {
+            None
+        };
+
+        tracing::info!(
+            "Repository: Found {} courses (page of {} total)",
+            courses.len(),
+            total
+        );
+        Ok(Page {
+            items: courses,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn count(&self) -> Result<u64, CourseRepositoryError> {
+        self.collection
+            .estimated_document_count()
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to count courses: {}", e);
+                CourseRepositoryError::Unexpected
+            })
     }
 
     async fn find_by_title(&self, title: &str) -> Result<Option<Course>, CourseRepositoryError> {
@@ -175,13 +541,18 @@ impl CourseRepository for MongoDbCourseRepository {
             CourseRepositoryError::Unexpected
         })?;
 
-        let course: Option<Course> = course_doc
-            .map(|doc| doc.try_into())
-            .transpose()
-            .map_err(|e| {
-                tracing::error!("Repository: Failed to deserialize course by title '{}': {}", title, e);
-                CourseRepositoryError::Unexpected
-            })?;
+        let course: Option<Course> =
+            course_doc
+                .map(|doc| doc.try_into())
+                .transpose()
+                .map_err(|e| {
+                    tracing::error!(
+                        "Repository: Failed to deserialize course by title '{}': {}",
+                        title,
+                        e
+                    );
+                    CourseRepositoryError::Unexpected
+                })?;
 
         match &course {
             Some(c) => tracing::debug!("Repository: Found course '{}' by title", c.title()),
@@ -190,4 +561,32 @@ impl CourseRepository for MongoDbCourseRepository {
 
         Ok(course)
     }
+
+    async fn find_by_pill_id(&self, pill_id: PillId) -> Result<Vec<Course>, CourseRepositoryError> {
+        let filter = doc! { "pill_ids": pill_id.to_string() };
+
+        let course_docs = self.collection.find(filter, None).await.map_err(|e| {
+            tracing::error!(
+                "Repository: Failed to find courses referencing pill {}: {}",
+                pill_id,
+                e
+            );
+            CourseRepositoryError::Unexpected
+        })?;
+
+        let courses: Result<Vec<Course>, _> =
+            course_docs.into_iter().map(|doc| doc.try_into()).collect();
+
+        let courses = courses.map_err(|e| {
+            tracing::error!("Repository: Failed to deserialize courses: {}", e);
+            CourseRepositoryError::Unexpected
+        })?;
+
+        tracing::debug!(
+            "Repository: Found {} courses referencing pill {}",
+            courses.len(),
+            pill_id
+        );
+        Ok(courses)
+    }
 }
@@ -0,0 +1,643 @@
+use async_trait::async_trait;
+use rusqlite::{
+    params, params_from_iter, types::Value as SqlValue, Connection, OptionalExtension, Row,
+};
+
+use crate::courses::domain::course::Difficulty;
+use crate::courses::domain::{
+    Course, CourseDetails, CourseFilter, CourseId, CourseRepository, CourseRepositoryError,
+    CourseSort, CourseSortField, Section, SectionId,
+};
+use crate::database::sqlite::{after_cursor, cursor_value, order_by, SqliteDatabase};
+use crate::database::DatabaseError;
+use crate::pagination::{Page, PageRequest};
+use crate::pills::domain::PillId;
+
+const COURSE_COLUMNS: &str =
+    "id, title, description, instructor, difficulty, hours, tags, price, version";
+
+/// A `courses` row with its sections and their pills, as read from SQLite.
+struct CourseRow {
+    id: String,
+    title: String,
+    description: String,
+    instructor: String,
+    difficulty: String,
+    hours: i8,
+    tags: String,
+    price: f64,
+    version: i64,
+    sections: Vec<SectionRow>,
+}
+
+struct SectionRow {
+    id: String,
+    title: String,
+    position: usize,
+    pill_ids: Vec<String>,
+}
+
+impl CourseRow {
+    fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            title: row.get("title")?,
+            description: row.get("description")?,
+            instructor: row.get("instructor")?,
+            difficulty: row.get("difficulty")?,
+            hours: row.get("hours")?,
+            tags: row.get("tags")?,
+            price: row.get("price")?,
+            version: row.get("version")?,
+            sections: Vec::new(),
+        })
+    }
+
+    /// Loads the course's sections in order, each with its pills in curriculum order.
+    fn load_sections(&mut self, connection: &Connection) -> rusqlite::Result<()> {
+        let mut statement = connection.prepare_cached(
+            "SELECT id, title, position FROM course_sections
+             WHERE course_id = ?1 ORDER BY position",
+        )?;
+        self.sections = statement
+            .query_map(params![self.id], |row| {
+                Ok(SectionRow {
+                    id: row.get("id")?,
+                    title: row.get("title")?,
+                    position: row.get("position")?,
+                    pill_ids: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut statement = connection.prepare_cached(
+            "SELECT pill_id, section_id FROM course_pills
+             WHERE course_id = ?1 ORDER BY position",
+        )?;
+        let memberships = statement
+            .query_map(params![self.id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (pill_id, section_id) in memberships {
+            if let Some(section) = self.sections.iter_mut().find(|s| s.id == section_id) {
+                section.pill_ids.push(pill_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<CourseRow> for Course {
+    type Error = String;
+
+    fn try_from(row: CourseRow) -> Result<Self, Self::Error> {
+        let id = row.id.parse::<CourseId>().map_err(|e| e.to_string())?;
+        let difficulty: Difficulty =
+            serde_json::from_value(serde_json::Value::String(row.difficulty))
+                .map_err(|e| e.to_string())?;
+        let tags: Vec<String> = serde_json::from_str(&row.tags).map_err(|e| e.to_string())?;
+
+        let mut sections = Vec::with_capacity(row.sections.len());
+        for section in row.sections {
+            let section_id = section.id.parse::<SectionId>().map_err(|e| e.to_string())?;
+            let pill_ids: Result<Vec<PillId>, _> = section
+                .pill_ids
+                .iter()
+                .map(|s| s.parse::<PillId>())
+                .collect();
+            sections.push(Section::create(
+                section_id,
+                section.title,
+                section.position,
+                pill_ids.map_err(|e| e.to_string())?,
+            ));
+        }
+
+        let details = CourseDetails {
+            title: row.title,
+            description: row.description,
+            instructor: row.instructor,
+            difficulty,
+            hours: row.hours,
+            tags,
+            price: row.price as f32,
+        };
+
+        Ok(Course::create(id, details, sections).with_version(row.version as u64))
+    }
+}
+
+/// Runs a `SELECT` over `courses` and loads the sections of every matching course.
+fn query_courses(
+    connection: &Connection,
+    sql: &str,
+    params: Vec<SqlValue>,
+) -> rusqlite::Result<Vec<CourseRow>> {
+    let mut statement = connection.prepare(sql)?;
+    let mut rows = statement
+        .query_map(params_from_iter(params), CourseRow::from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for row in &mut rows {
+        row.load_sections(connection)?;
+    }
+    Ok(rows)
+}
+
+fn to_courses(rows: Vec<CourseRow>) -> Result<Vec<Course>, CourseRepositoryError> {
+    let courses: Result<Vec<Course>, _> = rows.into_iter().map(Course::try_from).collect();
+
+    courses.map_err(|e| {
+        tracing::error!("Repository: Failed to deserialize courses: {}", e);
+        CourseRepositoryError::Unexpected
+    })
+}
+
+fn unexpected(action: &str) -> impl FnOnce(DatabaseError) -> CourseRepositoryError + '_ {
+    move |e| {
+        tracing::error!("Repository: Failed to {}: {}", action, e);
+        CourseRepositoryError::Unexpected
+    }
+}
+
+/// Outcome of a pill membership change made inside one transaction.
+enum Membership {
+    Saved(i64),
+    CourseNotFound,
+    PillNotInCourse,
+    VersionMismatch,
+}
+
+impl Membership {
+    fn into_version(self) -> Result<u64, CourseRepositoryError> {
+        match self {
+            Membership::Saved(version) => Ok(version as u64),
+            Membership::CourseNotFound | Membership::PillNotInCourse => {
+                Err(CourseRepositoryError::NotFound)
+            }
+            Membership::VersionMismatch => Err(CourseRepositoryError::ConcurrencyConflict),
+        }
+    }
+}
+
+/// The stored version of the course, or why a membership change can't go ahead.
+fn check_version(
+    connection: &Connection,
+    id: &str,
+    expected_version: Option<u64>,
+) -> rusqlite::Result<Result<i64, Membership>> {
+    let version: Option<i64> = connection
+        .query_row(
+            "SELECT version FROM courses WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    Ok(match version {
+        None => Err(Membership::CourseNotFound),
+        Some(version) if expected_version.is_some_and(|expected| expected as i64 != version) => {
+            Err(Membership::VersionMismatch)
+        }
+        Some(version) => Ok(version),
+    })
+}
+
+fn sort_column(field: CourseSortField) -> &'static str {
+    match field {
+        CourseSortField::Title => "title",
+        CourseSortField::Instructor => "instructor",
+        CourseSortField::Price => "price",
+        CourseSortField::Hours => "hours",
+    }
+}
+
+/// Builds the `WHERE` conditions for `filter` and their parameters.
+fn filter_clause(filter: &CourseFilter) -> (Vec<String>, Vec<SqlValue>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+
+    if let Some(instructor) = &filter.instructor {
+        conditions.push("instructor = ?".to_string());
+        params.push(SqlValue::Text(instructor.clone()));
+    }
+
+    if let Some(difficulty) = filter.difficulty {
+        conditions.push("difficulty = ?".to_string());
+        params.push(SqlValue::Text(difficulty.to_string()));
+    }
+
+    if let Some(tag) = &filter.tag {
+        conditions
+            .push("EXISTS (SELECT 1 FROM json_each(courses.tags) WHERE value = ?)".to_string());
+        params.push(SqlValue::Text(tag.clone()));
+    }
+
+    if let Some(min_price) = filter.min_price {
+        conditions.push("price >= ?".to_string());
+        params.push(SqlValue::Real(f64::from(min_price)));
+    }
+
+    if let Some(max_price) = filter.max_price {
+        conditions.push("price <= ?".to_string());
+        params.push(SqlValue::Real(f64::from(max_price)));
+    }
+
+    (conditions, params)
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+/// Writes spanning several statements run in a savepoint, so they nest inside
+/// the transaction of a unit of work.
+pub struct SqliteCourseRepository {
+    database: SqliteDatabase,
+}
+
+impl SqliteCourseRepository {
+    pub fn new(database: &SqliteDatabase) -> Self {
+        Self {
+            database: database.clone(),
+        }
+    }
+
+    async fn find_one(
+        &self,
+        condition: &str,
+        param: String,
+        action: &str,
+    ) -> Result<Option<Course>, CourseRepositoryError> {
+        let sql = format!("SELECT {} FROM courses WHERE {}", COURSE_COLUMNS, condition);
+        let rows = self
+            .database
+            .call(move |connection| query_courses(connection, &sql, vec![SqlValue::Text(param)]))
+            .await
+            .map_err(unexpected(action))?;
+
+        Ok(to_courses(rows)?.into_iter().next())
+    }
+}
+
+#[async_trait]
+impl CourseRepository for SqliteCourseRepository {
+    async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError> {
+        let expected = course.version() as i64;
+        let id = course.id().to_string();
+        let title = course.title().to_string();
+        let description = course.description().to_string();
+        let instructor = course.instructor().to_string();
+        let difficulty = course.difficulty().to_string();
+        let hours = course.hours();
+        let tags = serde_json::to_string(course.tags()).map_err(|e| {
+            tracing::error!(
+                "Repository: Failed to serialize tags of course {}: {}",
+                course.id(),
+                e
+            );
+            CourseRepositoryError::Unexpected
+        })?;
+        let price = f64::from(course.price());
+        let sections: Vec<(String, String, usize, Vec<String>)> = course
+            .sections()
+            .iter()
+            .map(|section| {
+                (
+                    section.id().to_string(),
+                    section.title().to_string(),
+                    section.position(),
+                    section.pill_ids().iter().map(|id| id.to_string()).collect(),
+                )
+            })
+            .collect();
+
+        // Only a new course may be inserted; an existing row is updated only while
+        // it is still at the expected version. Nothing else is written otherwise.
+        let saved = self
+            .database
+            .call(move |connection| {
+                let tx = connection.savepoint()?;
+                let changed = if expected == 0 {
+                    tx.execute(
+                        "INSERT INTO courses (id, title, description, instructor, difficulty, hours, tags, price, version)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1)
+                         ON CONFLICT (id) DO UPDATE SET
+                            title = excluded.title,
+                            description = excluded.description,
+                            instructor = excluded.instructor,
+                            difficulty = excluded.difficulty,
+                            hours = excluded.hours,
+                            tags = excluded.tags,
+                            price = excluded.price,
+                            version = 1
+                         WHERE courses.version = 0",
+                        params![id, title, description, instructor, difficulty, hours, tags, price],
+                    )?
+                } else {
+                    tx.execute(
+                        "UPDATE courses SET
+                            title = ?2,
+                            description = ?3,
+                            instructor = ?4,
+                            difficulty = ?5,
+                            hours = ?6,
+                            tags = ?7,
+                            price = ?8,
+                            version = ?9 + 1
+                         WHERE id = ?1 AND version = ?9",
+                        params![id, title, description, instructor, difficulty, hours, tags, price, expected],
+                    )?
+                };
+                if changed == 0 {
+                    return Ok(false);
+                }
+
+                tx.execute("DELETE FROM course_pills WHERE course_id = ?1", params![id])?;
+                tx.execute("DELETE FROM course_sections WHERE course_id = ?1", params![id])?;
+
+                let mut position = 0;
+                for (section_id, section_title, section_position, pill_ids) in sections {
+                    tx.execute(
+                        "INSERT INTO course_sections (course_id, id, title, position)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![id, section_id, section_title, section_position],
+                    )?;
+                    for pill_id in pill_ids {
+                        tx.execute(
+                            "INSERT INTO course_pills (course_id, pill_id, section_id, position)
+                             VALUES (?1, ?2, ?3, ?4)",
+                            params![id, pill_id, section_id, position],
+                        )?;
+                        position += 1;
+                    }
+                }
+
+                tx.commit()?;
+                Ok(true)
+            })
+            .await
+            .map_err(unexpected("save course"))?;
+
+        if !saved {
+            tracing::warn!(
+                "Repository: Course {} is no longer at version {}",
+                course.id(),
+                expected
+            );
+            return Err(CourseRepositoryError::ConcurrencyConflict);
+        }
+        course.set_version(expected as u64 + 1);
+
+        tracing::info!(
+            "Repository: Course {} saved at version {}",
+            course.id(),
+            course.version()
+        );
+        Ok(())
+    }
+
+    async fn add_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let id = course_id.to_string();
+        let pill = pill_id.to_string();
+        let default_section = Course::default_section(course_id, Vec::new());
+        let default_section_id = default_section.id().to_string();
+        let default_section_title = default_section.title().to_string();
+
+        let outcome = self
+            .database
+            .call(move |connection| {
+                let tx = connection.savepoint()?;
+                let version = match check_version(&tx, &id, expected_version)? {
+                    Ok(version) => version,
+                    Err(outcome) => return Ok(outcome),
+                };
+
+                let present: bool = tx.query_row(
+                    "SELECT COUNT(*) > 0 FROM course_pills WHERE course_id = ?1 AND pill_id = ?2",
+                    params![id, pill],
+                    |row| row.get(0),
+                )?;
+                if present {
+                    return Ok(Membership::Saved(version));
+                }
+
+                let last_section: Option<String> = tx
+                    .query_row(
+                        "SELECT id FROM course_sections
+                         WHERE course_id = ?1 ORDER BY position DESC LIMIT 1",
+                        params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                let section_id = match last_section {
+                    Some(section_id) => section_id,
+                    None => {
+                        tx.execute(
+                            "INSERT INTO course_sections (course_id, id, title, position)
+                             VALUES (?1, ?2, ?3, 0)",
+                            params![id, default_section_id, default_section_title],
+                        )?;
+                        default_section_id
+                    }
+                };
+
+                // Positions are global to the course, so the pill lands at the
+                // end of the curriculum, which is the end of the last section.
+                tx.execute(
+                    "INSERT INTO course_pills (course_id, pill_id, section_id, position)
+                     SELECT ?1, ?2, ?3, COALESCE(MAX(position) + 1, 0)
+                     FROM course_pills WHERE course_id = ?1",
+                    params![id, pill, section_id],
+                )?;
+                tx.execute(
+                    "UPDATE courses SET version = version + 1 WHERE id = ?1",
+                    params![id],
+                )?;
+                tx.commit()?;
+                Ok(Membership::Saved(version + 1))
+            })
+            .await
+            .map_err(unexpected("add pill to course"))?;
+
+        let version = outcome.into_version()?;
+        tracing::info!("Repository: Pill {} added to course {}", pill_id, course_id);
+        Ok(version)
+    }
+
+    async fn remove_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let id = course_id.to_string();
+        let pill = pill_id.to_string();
+
+        let outcome = self
+            .database
+            .call(move |connection| {
+                let tx = connection.savepoint()?;
+                let version = match check_version(&tx, &id, expected_version)? {
+                    Ok(version) => version,
+                    Err(outcome) => return Ok(outcome),
+                };
+
+                let removed = tx.execute(
+                    "DELETE FROM course_pills WHERE course_id = ?1 AND pill_id = ?2",
+                    params![id, pill],
+                )?;
+                if removed == 0 {
+                    return Ok(Membership::PillNotInCourse);
+                }
+
+                tx.execute(
+                    "UPDATE courses SET version = version + 1 WHERE id = ?1",
+                    params![id],
+                )?;
+                tx.commit()?;
+                Ok(Membership::Saved(version + 1))
+            })
+            .await
+            .map_err(unexpected("remove pill from course"))?;
+
+        let version = outcome.into_version()?;
+        tracing::info!(
+            "Repository: Pill {} removed from course {}",
+            pill_id,
+            course_id
+        );
+        Ok(version)
+    }
+
+    async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError> {
+        let course = self
+            .find_one("id = ?", id.to_string(), "find course")
+            .await?;
+
+        match &course {
+            Some(_) => tracing::debug!("Repository: Found course {}", id),
+            None => tracing::debug!("Repository: Course {} not found", id),
+        }
+
+        Ok(course)
+    }
+
+    async fn find_page(
+        &self,
+        filter: &CourseFilter,
+        sort: CourseSort,
+        page: &PageRequest,
+    ) -> Result<Page<Course>, CourseRepositoryError> {
+        let column = sort_column(sort.field);
+        let (mut conditions, mut params) = filter_clause(filter);
+        let count_sql = format!("SELECT COUNT(*) FROM courses {}", where_clause(&conditions));
+        let count_params = params.clone();
+
+        if let Some(cursor) = &page.cursor {
+            let value = cursor_value(cursor).ok_or(CourseRepositoryError::InvalidCursor)?;
+            conditions.push(after_cursor(column, sort.direction));
+            params.extend([value.clone(), value, SqlValue::Text(cursor.id.clone())]);
+        }
+        params.push(SqlValue::Integer(i64::from(page.limit) + 1));
+
+        let select_sql = format!(
+            "SELECT {} FROM courses {} {} LIMIT ?",
+            COURSE_COLUMNS,
+            where_clause(&conditions),
+            order_by(column, sort.direction)
+        );
+
+        let (total, rows) = self
+            .database
+            .call(move |connection| {
+                let total: u64 =
+                    connection
+                        .query_row(&count_sql, params_from_iter(count_params), |row| row.get(0))?;
+                let rows = query_courses(connection, &select_sql, params)?;
+                Ok((total, rows))
+            })
+            .await
+            .map_err(unexpected("load courses page"))?;
+
+        let mut courses = to_courses(rows)?;
+        let next_cursor = if courses.len() > page.limit as usize {
+            courses.truncate(page.limit as usize);
+            courses.last().map(|c| sort.cursor_after(c).encode())
+        } else {
+            None
+        };
+
+        tracing::info!(
+            "Repository: Found {} courses (page of {} total)",
+            courses.len(),
+            total
+        );
+        Ok(Page {
+            items: courses,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn count(&self) -> Result<u64, CourseRepositoryError> {
+        self.database
+            .call(|connection| {
+                connection.query_row("SELECT COUNT(*) FROM courses", [], |row| row.get(0))
+            })
+            .await
+            .map_err(unexpected("count courses"))
+    }
+
+    async fn find_by_title(&self, title: &str) -> Result<Option<Course>, CourseRepositoryError> {
+        let course = self
+            .find_one(
+                "title = ? ORDER BY rowid LIMIT 1",
+                title.to_string(),
+                "find course by title",
+            )
+            .await?;
+
+        match &course {
+            Some(c) => tracing::debug!("Repository: Found course '{}' by title", c.title()),
+            None => tracing::debug!("Repository: Course with title '{}' not found", title),
+        }
+
+        Ok(course)
+    }
+
+    async fn find_by_pill_id(&self, pill_id: PillId) -> Result<Vec<Course>, CourseRepositoryError> {
+        let sql = format!(
+            "SELECT {} FROM courses
+             WHERE id IN (SELECT course_id FROM course_pills WHERE pill_id = ?)
+             ORDER BY rowid",
+            COURSE_COLUMNS
+        );
+        let param = SqlValue::Text(pill_id.to_string());
+
+        let rows = self
+            .database
+            .call(move |connection| query_courses(connection, &sql, vec![param]))
+            .await
+            .map_err(unexpected("find courses referencing pill"))?;
+
+        let courses = to_courses(rows)?;
+        tracing::debug!(
+            "Repository: Found {} courses referencing pill {}",
+            courses.len(),
+            pill_id
+        );
+        Ok(courses)
+    }
+}
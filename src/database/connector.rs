@@ -0,0 +1,145 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::migrations;
+use super::transaction::{supports_transactions, MongoUnitOfWork};
+use super::{DatabaseConfig, DatabaseError};
+
+/// Delay before the second connection attempt; it doubles after each failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Whether MongoDB has been reached and initialized, shared between the
+/// background connector and the health checks.
+#[derive(Clone, Default)]
+pub struct ConnectionState {
+    ready: Arc<AtomicBool>,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl ConnectionState {
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// Why the last connection attempt failed, if it did.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    fn set_ready(&self) {
+        *self.last_error.lock().unwrap() = None;
+        self.ready.store(true, Ordering::SeqCst);
+    }
+
+    fn set_error(&self, error: String) {
+        *self.last_error.lock().unwrap() = Some(error);
+    }
+}
+
+/// Connects to MongoDB in the background, retrying with exponential backoff
+/// up to `max_backoff`, so the server can start while the database is down.
+/// Once connected it applies migrations, creates indexes and enables
+/// transactions on `unit_of_work` if the server supports them, then marks
+/// `state` ready. After that the driver handles reconnects itself. Index
+/// creation doesn't hold up readiness: if it fails, it is retried with the
+/// same backoff until it succeeds.
+pub fn spawn(
+    db_config: DatabaseConfig,
+    unit_of_work: Arc<MongoUnitOfWork>,
+    state: ConnectionState,
+    max_backoff: Duration,
+) {
+    tokio::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF.min(max_backoff);
+        let mut attempt = 1;
+
+        let indexes_created = loop {
+            match initialize(&db_config, &unit_of_work).await {
+                Ok(indexes_created) => {
+                    tracing::info!(
+                        "MongoDB: Connected and initialized after {} attempts",
+                        attempt
+                    );
+                    state.set_ready();
+                    break indexes_created;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "MongoDB: Attempt {} to connect failed, retrying in {:?}: {}",
+                        attempt,
+                        backoff,
+                        e
+                    );
+                    state.set_error(e.to_string());
+                }
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(max_backoff);
+            attempt += 1;
+        };
+
+        if !indexes_created {
+            retry_indexes(&db_config, max_backoff).await;
+        }
+    });
+}
+
+/// Keeps trying to create the indexes after the first attempt failed, e.g.
+/// because an existing index conflicts until someone drops it.
+async fn retry_indexes(db_config: &DatabaseConfig, max_backoff: Duration) {
+    let mut backoff = INITIAL_BACKOFF.min(max_backoff);
+    let mut attempt = 2;
+
+    loop {
+        tokio::time::sleep(backoff).await;
+
+        match db_config.initialize_indexes().await {
+            Ok(()) => {
+                tracing::info!("MongoDB: Indexes created after {} attempts", attempt);
+                return;
+            }
+            Err(e) => tracing::warn!(
+                "MongoDB: Attempt {} to create indexes failed, retrying in {:?}: {}",
+                attempt,
+                (backoff * 2).min(max_backoff),
+                e
+            ),
+        }
+
+        backoff = (backoff * 2).min(max_backoff);
+        attempt += 1;
+    }
+}
+
+/// Returns whether the indexes were created; failing to create them doesn't
+/// fail initialization.
+async fn initialize(
+    db_config: &DatabaseConfig,
+    unit_of_work: &MongoUnitOfWork,
+) -> Result<bool, DatabaseError> {
+    db_config.ping().await?;
+
+    migrations::run(db_config.get_database()).await?;
+
+    // Create database indexes for better performance
+    let indexes_created = match db_config.initialize_indexes().await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Failed to create database indexes, will retry: {}", e);
+            tracing::warn!("Application will continue but performance may be affected");
+            false
+        }
+    };
+
+    let transactions = supports_transactions(db_config.get_database()).await;
+    if !transactions {
+        tracing::warn!(
+            "MongoDB: Not a replica set, multi-aggregate operations will run without transactions"
+        );
+    }
+    unit_of_work.set_transactions_supported(transactions);
+
+    Ok(indexes_created)
+}
@@ -11,26 +11,27 @@ pub enum DatabaseError {
     #[error("Deserialization failed: {0}")]
     DeserializationFailed(#[from] bson::de::Error),
 
-    #[error("Document not found")]
-    NotFound,
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
 
     #[error("Duplicate document")]
     DuplicateKey,
 
-    #[error("Invalid document format")]
-    InvalidFormat,
-
     #[error("Database operation failed: {message}")]
     OperationFailed { message: String },
 
     #[error("Transaction failed")]
     TransactionFailed,
 
-    #[error("Index creation failed")]
-    IndexCreationFailed,
+    #[error("Migration {version} ({name}) failed: {message}")]
+    MigrationFailed {
+        version: u32,
+        name: &'static str,
+        message: String,
+    },
 
-    #[error("Unknown database error")]
-    Unknown,
+    #[error("Migrations are locked by another instance ({owner})")]
+    MigrationLocked { owner: String },
 }
 
 impl DatabaseError {
@@ -39,14 +40,6 @@ impl DatabaseError {
             message: message.into(),
         }
     }
-
-    pub fn is_not_found(&self) -> bool {
-        matches!(self, Self::NotFound)
-    }
-
-    pub fn is_duplicate_key(&self) -> bool {
-        matches!(self, Self::DuplicateKey)
-    }
 }
 
 // Helper function to convert MongoDB errors to our custom error type
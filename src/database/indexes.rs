@@ -2,10 +2,21 @@ use crate::database::DatabaseError;
 use mongodb::bson::doc;
 use mongodb::{Database, IndexModel};
 
+/// Index names `create_all_indexes` creates, per collection, as MongoDB names
+/// them by default. The health check reports any that are missing.
+pub const EXPECTED_INDEXES: &[(&str, &[&str])] = &[
+    ("pills", &["title_1", "title_text_content_text"]),
+    (
+        "courses",
+        &["title_1", "instructor_1", "title_text_description_text"],
+    ),
+];
+
 /// Creates all necessary database indexes for optimal query performance
 pub async fn create_all_indexes(database: &Database) -> Result<(), DatabaseError> {
     create_pills_indexes(database).await?;
     create_courses_indexes(database).await?;
+    create_text_indexes(database).await?;
 
     tracing::info!("Database: All indexes created successfully");
     Ok(())
@@ -71,7 +82,6 @@ pub async fn create_compound_indexes(database: &Database) -> Result<(), Database
 }
 
 /// Creates text indexes for full-text search capabilities
-#[allow(dead_code)]
 pub async fn create_text_indexes(database: &Database) -> Result<(), DatabaseError> {
     let pills_collection = database.collection::<mongodb::bson::Document>("pills");
     let courses_collection = database.collection::<mongodb::bson::Document>("courses");
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use mongodb::bson::{doc, Document};
+use mongodb::Database;
+
+use super::Migration;
+use crate::database::DatabaseError;
+
+/// Sets `version: 0` on documents stored before optimistic concurrency, so
+/// version filters can match them with a plain equality.
+pub struct BackfillVersions {
+    version: u32,
+    name: &'static str,
+    collection: &'static str,
+}
+
+impl BackfillVersions {
+    pub fn new(version: u32, name: &'static str, collection: &'static str) -> Self {
+        Self {
+            version,
+            name,
+            collection,
+        }
+    }
+
+    fn filter() -> Document {
+        doc! { "version": { "$exists": false } }
+    }
+}
+
+#[async_trait]
+impl Migration for BackfillVersions {
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn pending(&self, database: &Database) -> Result<u64, DatabaseError> {
+        Ok(database
+            .collection::<Document>(self.collection)
+            .count_documents(Self::filter(), None)
+            .await?)
+    }
+
+    async fn up(&self, database: &Database) -> Result<u64, DatabaseError> {
+        let result = database
+            .collection::<Document>(self.collection)
+            .update_many(Self::filter(), doc! { "$set": { "version": 0_i64 } }, None)
+            .await?;
+        Ok(result.modified_count)
+    }
+}
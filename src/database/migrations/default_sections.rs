@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use mongodb::bson::{doc, Document};
+use mongodb::Database;
+
+use super::Migration;
+use crate::courses::domain::course::DEFAULT_SECTION_TITLE;
+use crate::database::DatabaseError;
+
+/// Gives courses stored before sections existed a `sections` field: their flat
+/// `pill_ids` become the default section, whose ID is the course ID, exactly
+/// as the repository already presents them when reading.
+pub struct DefaultSections;
+
+impl DefaultSections {
+    fn filter() -> Document {
+        doc! { "sections": { "$exists": false } }
+    }
+}
+
+#[async_trait]
+impl Migration for DefaultSections {
+    fn version(&self) -> u32 {
+        3
+    }
+
+    fn name(&self) -> &'static str {
+        "add_default_course_sections"
+    }
+
+    async fn pending(&self, database: &Database) -> Result<u64, DatabaseError> {
+        Ok(database
+            .collection::<Document>("courses")
+            .count_documents(Self::filter(), None)
+            .await?)
+    }
+
+    async fn up(&self, database: &Database) -> Result<u64, DatabaseError> {
+        let default_section = doc! {
+            "id": "$_id",
+            "title": DEFAULT_SECTION_TITLE,
+            "position": 0_i64,
+            "pill_ids": "$pill_ids",
+        };
+        let pipeline = vec![doc! {
+            "$set": {
+                "sections": {
+                    "$cond": [
+                        { "$gt": [{ "$size": "$pill_ids" }, 0] },
+                        [default_section],
+                        []
+                    ]
+                }
+            }
+        }];
+
+        let result = database
+            .collection::<Document>("courses")
+            .update_many(Self::filter(), pipeline, None)
+            .await?;
+        Ok(result.modified_count)
+    }
+}
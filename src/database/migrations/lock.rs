@@ -0,0 +1,159 @@
+use mongodb::bson::{doc, DateTime, Document};
+use mongodb::{Collection, Database};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::database::error::map_mongodb_error;
+use crate::database::DatabaseError;
+
+const LOCK_COLLECTION: &str = "_migrations_lock";
+const LOCK_ID: &str = "migrations";
+
+/// How long the lock is held without being extended. An instance that crashes
+/// mid-migration blocks the others for at most this long.
+const LEASE: Duration = Duration::from_secs(300);
+
+/// How often the lease is renewed while migrations run, well within `LEASE`
+/// so a renewal that fails once or runs late doesn't let it expire.
+const RENEW_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long to wait for another instance to finish before giving up.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(600);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A lease on a single document in `_migrations_lock`, so only one instance
+/// applies migrations at a time.
+#[derive(Clone)]
+pub struct MigrationLock {
+    collection: Collection<Document>,
+    owner: String,
+}
+
+impl MigrationLock {
+    /// Waits until no other instance holds an unexpired lease, then takes it.
+    pub async fn acquire(database: &Database) -> Result<Self, DatabaseError> {
+        let lock = Self {
+            collection: database.collection(LOCK_COLLECTION),
+            owner: Uuid::new_v4().to_string(),
+        };
+        let started = Instant::now();
+
+        loop {
+            if lock.try_acquire().await? {
+                tracing::info!("Migrations: Acquired lock as {}", lock.owner);
+                return Ok(lock);
+            }
+
+            let holder = lock.holder().await.unwrap_or_default();
+            if started.elapsed() >= WAIT_TIMEOUT {
+                return Err(DatabaseError::MigrationLocked { owner: holder });
+            }
+
+            tracing::info!("Migrations: Waiting for lock held by {}", holder);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Takes the lock if it is free or its lease expired. While another
+    /// instance holds it the filter matches nothing and the upsert collides
+    /// with the existing document.
+    async fn try_acquire(&self) -> Result<bool, DatabaseError> {
+        let now = DateTime::now();
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": LOCK_ID, "expires_at": { "$lt": now } },
+                doc! {
+                    "$set": {
+                        "owner": &self.owner,
+                        "locked_at": now,
+                        "expires_at": Self::expiry(),
+                    }
+                },
+                mongodb::options::UpdateOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await;
+
+        match result.map_err(map_mongodb_error) {
+            Ok(_) => Ok(true),
+            Err(DatabaseError::DuplicateKey) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn holder(&self) -> Option<String> {
+        let lock = self
+            .collection
+            .find_one(doc! { "_id": LOCK_ID }, None)
+            .await
+            .ok()??;
+        lock.get_str("owner").ok().map(str::to_string)
+    }
+
+    /// Renews the lease every `RENEW_INTERVAL` until the returned guard is
+    /// dropped, so a single migration that runs longer than `LEASE`, such as a
+    /// backfill of a large collection, isn't taken over by another instance.
+    pub fn keep_alive(&self) -> LeaseRenewal {
+        let lock = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(RENEW_INTERVAL).await;
+                match lock.extend().await {
+                    Ok(()) => tracing::debug!("Migrations: Renewed lock"),
+                    Err(DatabaseError::MigrationLocked { .. }) => return,
+                    Err(e) => tracing::warn!("Migrations: Failed to renew lock: {}", e),
+                }
+            }
+        });
+        LeaseRenewal { task }
+    }
+
+    /// Renews the lease now, failing if another instance has taken it over.
+    pub async fn extend(&self) -> Result<(), DatabaseError> {
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": LOCK_ID, "owner": &self.owner },
+                doc! { "$set": { "expires_at": Self::expiry() } },
+                None,
+            )
+            .await?;
+
+        if result.matched_count == 0 {
+            tracing::error!("Migrations: Lost the lock, another instance took it over");
+            return Err(DatabaseError::MigrationLocked {
+                owner: self.holder().await.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
+    pub async fn release(self) {
+        match self
+            .collection
+            .delete_one(doc! { "_id": LOCK_ID, "owner": &self.owner }, None)
+            .await
+        {
+            Ok(_) => tracing::info!("Migrations: Released lock"),
+            Err(e) => tracing::warn!("Migrations: Failed to release lock: {}", e),
+        }
+    }
+
+    fn expiry() -> DateTime {
+        DateTime::from_millis(DateTime::now().timestamp_millis() + LEASE.as_millis() as i64)
+    }
+}
+
+/// Stops renewing the lease when dropped.
+pub struct LeaseRenewal {
+    task: JoinHandle<()>,
+}
+
+impl Drop for LeaseRenewal {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
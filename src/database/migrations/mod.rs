@@ -0,0 +1,194 @@
+//! Ordered schema migrations for the MongoDB collections.
+//!
+//! Pending migrations are applied at startup in version order while holding
+//! [`MigrationLock`], and each applied migration is recorded in the
+//! `_migrations` collection so it never runs twice.
+
+mod backfill_versions;
+mod default_sections;
+mod lock;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::bson::DateTime;
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::database::DatabaseError;
+use backfill_versions::BackfillVersions;
+use default_sections::DefaultSections;
+pub use lock::MigrationLock;
+
+const MIGRATIONS_COLLECTION: &str = "_migrations";
+
+#[async_trait]
+pub trait Migration: Send + Sync {
+    fn version(&self) -> u32;
+    fn name(&self) -> &'static str;
+    /// How many documents `up` would change; used for dry runs.
+    async fn pending(&self, database: &Database) -> Result<u64, DatabaseError>;
+    /// Applies the migration and returns how many documents changed. Must be
+    /// idempotent: a run interrupted before it was recorded is repeated.
+    async fn up(&self, database: &Database) -> Result<u64, DatabaseError>;
+}
+
+/// Every migration, in the order they are applied. Versions must increase and
+/// never be reused; add new migrations at the end.
+fn all() -> Vec<Box<dyn Migration>> {
+    vec![
+        Box::new(BackfillVersions::new(1, "backfill_pill_versions", "pills")),
+        Box::new(BackfillVersions::new(
+            2,
+            "backfill_course_versions",
+            "courses",
+        )),
+        Box::new(DefaultSections),
+    ]
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MigrationRecord {
+    #[serde(rename = "_id")]
+    version: i64,
+    name: String,
+    applied_at: DateTime,
+    modified: i64,
+}
+
+/// Whether a known migration has been applied, for `--migrations-status`.
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: &'static str,
+    pub applied_at: Option<DateTime>,
+}
+
+/// A migration that would run, for `--migrations-dry-run`.
+pub struct PlannedMigration {
+    pub version: u32,
+    pub name: &'static str,
+    pub documents: u64,
+}
+
+async fn applied(database: &Database) -> Result<HashMap<u32, MigrationRecord>, DatabaseError> {
+    let records: Vec<MigrationRecord> = database
+        .collection::<MigrationRecord>(MIGRATIONS_COLLECTION)
+        .find(None, None)
+        .await?
+        .try_collect()
+        .await?;
+
+    let known: Vec<u32> = all().iter().map(|m| m.version()).collect();
+    let mut applied = HashMap::new();
+    for record in records {
+        let version = record.version as u32;
+        if !known.contains(&version) {
+            tracing::warn!(
+                "Migrations: Database has migration {} ({}) which this build doesn't know about",
+                record.version,
+                record.name
+            );
+        }
+        applied.insert(version, record);
+    }
+    Ok(applied)
+}
+
+async fn pending_migrations(database: &Database) -> Result<Vec<Box<dyn Migration>>, DatabaseError> {
+    let applied = applied(database).await?;
+    Ok(all()
+        .into_iter()
+        .filter(|m| !applied.contains_key(&m.version()))
+        .collect())
+}
+
+/// Applies every pending migration. Other instances starting at the same time
+/// wait for the lock and then find nothing left to do.
+pub async fn run(database: &Database) -> Result<usize, DatabaseError> {
+    if pending_migrations(database).await?.is_empty() {
+        tracing::info!("Migrations: Database is up to date");
+        return Ok(0);
+    }
+
+    let lock = MigrationLock::acquire(database).await?;
+    let result = apply_pending(database, &lock).await;
+    lock.release().await;
+    result
+}
+
+async fn apply_pending(database: &Database, lock: &MigrationLock) -> Result<usize, DatabaseError> {
+    // Read again under the lock: another instance may have applied them meanwhile.
+    let pending = pending_migrations(database).await?;
+    let _renewal = lock.keep_alive();
+    let records = database.collection::<MigrationRecord>(MIGRATIONS_COLLECTION);
+
+    for migration in &pending {
+        tracing::info!(
+            "Migrations: Applying {} ({})",
+            migration.version(),
+            migration.name()
+        );
+        let started = Instant::now();
+
+        let modified =
+            migration
+                .up(database)
+                .await
+                .map_err(|e| DatabaseError::MigrationFailed {
+                    version: migration.version(),
+                    name: migration.name(),
+                    message: e.to_string(),
+                })?;
+
+        records
+            .insert_one(
+                MigrationRecord {
+                    version: migration.version() as i64,
+                    name: migration.name().to_string(),
+                    applied_at: DateTime::now(),
+                    modified: modified as i64,
+                },
+                None,
+            )
+            .await?;
+
+        tracing::info!(
+            "Migrations: Applied {} ({}), {} documents changed in {:?}",
+            migration.version(),
+            migration.name(),
+            modified,
+            started.elapsed()
+        );
+
+        // Stop if the lease was lost despite the renewals.
+        lock.extend().await?;
+    }
+
+    Ok(pending.len())
+}
+
+pub async fn status(database: &Database) -> Result<Vec<MigrationStatus>, DatabaseError> {
+    let applied = applied(database).await?;
+    Ok(all()
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version(),
+            name: m.name(),
+            applied_at: applied.get(&m.version()).map(|r| r.applied_at),
+        })
+        .collect())
+}
+
+/// Reports what `run` would do without taking the lock or changing anything.
+pub async fn dry_run(database: &Database) -> Result<Vec<PlannedMigration>, DatabaseError> {
+    let mut planned = Vec::new();
+    for migration in pending_migrations(database).await? {
+        planned.push(PlannedMigration {
+            version: migration.version(),
+            name: migration.name(),
+            documents: migration.pending(database).await?,
+        });
+    }
+    Ok(planned)
+}
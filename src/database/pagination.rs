@@ -0,0 +1,97 @@
+use mongodb::bson::{doc, Bson, Document};
+
+use crate::pagination::{Cursor, SortDirection};
+
+/// Sort specification for a page, using `_id` as a tie-breaker so the order is total.
+pub fn sort_document(sort_key: &str, direction: SortDirection) -> Document {
+    let order = match direction {
+        SortDirection::Asc => 1,
+        SortDirection::Desc => -1,
+    };
+
+    let mut sort = Document::new();
+    sort.insert(sort_key, order);
+    sort.insert("_id", order);
+    sort
+}
+
+/// Restricts `filter` to the documents that come after `cursor` in the given order.
+/// Returns `None` if the cursor value can't be represented as BSON.
+pub fn after_cursor(
+    filter: Document,
+    sort_key: &str,
+    direction: SortDirection,
+    cursor: &Cursor,
+) -> Option<Document> {
+    let value = Bson::try_from(cursor.value.clone()).ok()?;
+    let comparison = match direction {
+        SortDirection::Asc => "$gt",
+        SortDirection::Desc => "$lt",
+    };
+
+    let mut past_value = Document::new();
+    past_value.insert(sort_key, doc! { comparison: value.clone() });
+
+    let mut same_value = Document::new();
+    same_value.insert(sort_key, value);
+    same_value.insert("_id", doc! { comparison: cursor.id.clone() });
+
+    Some(doc! {
+        "$and": [
+            filter,
+            { "$or": [past_value, same_value] },
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sort_document_uses_id_as_tie_breaker() {
+        assert_eq!(
+            sort_document("price", SortDirection::Desc),
+            doc! { "price": -1, "_id": -1 }
+        );
+    }
+
+    #[test]
+    fn after_cursor_compares_id_only_for_equal_values() {
+        let cursor = Cursor::new("title", SortDirection::Asc, json!("Rust"), "42".to_string());
+
+        let filter = after_cursor(
+            doc! { "tags": "rust" },
+            "title",
+            SortDirection::Asc,
+            &cursor,
+        );
+
+        assert_eq!(
+            filter,
+            Some(doc! {
+                "$and": [
+                    { "tags": "rust" },
+                    { "$or": [
+                        { "title": { "$gt": "Rust" } },
+                        { "title": "Rust", "_id": { "$gt": "42" } },
+                    ] },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn after_cursor_uses_lt_when_descending() {
+        let cursor = Cursor::new("hours", SortDirection::Desc, json!(3), "7".to_string());
+
+        let filter = after_cursor(doc! {}, "hours", SortDirection::Desc, &cursor).unwrap();
+
+        let or = filter.get_array("$and").unwrap()[1].as_document().unwrap();
+        assert_eq!(
+            or.get_array("$or").unwrap()[1].as_document().unwrap(),
+            &doc! { "hours": 3, "_id": { "$lt": "7" } }
+        );
+    }
+}
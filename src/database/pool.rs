@@ -0,0 +1,50 @@
+use mongodb::event::cmap::{CmapEventHandler, ConnectionCheckedInEvent, ConnectionCheckedOutEvent};
+use mongodb::options::ServerAddress;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Counts connections checked out of the MongoDB pool, per server, from the
+/// driver's connection pool events. The driver exposes no pool statistics
+/// otherwise.
+#[derive(Clone, Default)]
+pub struct PoolMonitor {
+    checked_out: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl PoolMonitor {
+    /// Connections in use on the busiest server; `max_pool_size` applies per server.
+    pub fn busiest_server_in_use(&self) -> usize {
+        self.checked_out
+            .lock()
+            .unwrap()
+            .values()
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Connections in use on each server the pool has connected to.
+    pub fn in_use_per_server(&self) -> Vec<(String, usize)> {
+        self.checked_out
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(server, count)| (server.clone(), *count))
+            .collect()
+    }
+
+    fn update(&self, address: &ServerAddress, change: impl FnOnce(&mut usize)) {
+        let mut checked_out = self.checked_out.lock().unwrap();
+        change(checked_out.entry(address.to_string()).or_default());
+    }
+}
+
+impl CmapEventHandler for PoolMonitor {
+    fn handle_connection_checked_out_event(&self, event: ConnectionCheckedOutEvent) {
+        self.update(&event.address, |count| *count += 1);
+    }
+
+    fn handle_connection_checked_in_event(&self, event: ConnectionCheckedInEvent) {
+        self.update(&event.address, |count| *count = count.saturating_sub(1));
+    }
+}
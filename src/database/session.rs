@@ -0,0 +1,195 @@
+use futures::TryStreamExt;
+use mongodb::{
+    bson::Document,
+    error::{Error, Result, TRANSIENT_TRANSACTION_ERROR},
+    options::{
+        CountOptions, DeleteOptions, FindOneAndUpdateOptions, FindOneOptions, FindOptions,
+        UpdateOptions,
+    },
+    results::{DeleteResult, UpdateResult},
+    ClientSession, Collection,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
+
+/// A client session shared by the repositories of one transaction.
+pub struct MongoSession {
+    session: Mutex<ClientSession>,
+    transient_error: AtomicBool,
+}
+
+impl MongoSession {
+    pub fn new(session: ClientSession) -> Self {
+        Self {
+            session: Mutex::new(session),
+            transient_error: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn lock(&self) -> MutexGuard<'_, ClientSession> {
+        self.session.lock().await
+    }
+
+    /// Remembers whether `error` means the whole transaction may succeed if retried.
+    pub fn record(&self, error: &Error) {
+        if error.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+            self.transient_error.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub fn has_transient_error(&self) -> bool {
+        self.transient_error.load(Ordering::SeqCst)
+    }
+}
+
+/// A collection whose operations join a client session when it has one, so the
+/// same repository code runs both inside and outside a transaction.
+pub struct SessionCollection<T> {
+    collection: Collection<T>,
+    session: Option<Arc<MongoSession>>,
+}
+
+impl<T> SessionCollection<T>
+where
+    T: Serialize + DeserializeOwned + Unpin + Send + Sync,
+{
+    pub fn new(collection: Collection<T>, session: Option<Arc<MongoSession>>) -> Self {
+        Self {
+            collection,
+            session,
+        }
+    }
+
+    pub async fn find_one(
+        &self,
+        filter: Document,
+        options: impl Into<Option<FindOneOptions>>,
+    ) -> Result<Option<T>> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                self.collection
+                    .find_one_with_session(filter, options, &mut session)
+                    .await
+            }
+            None => self.collection.find_one(filter, options).await,
+        };
+        self.track(result)
+    }
+
+    /// Runs the query and collects every matching document.
+    pub async fn find(
+        &self,
+        filter: Document,
+        options: impl Into<Option<FindOptions>>,
+    ) -> Result<Vec<T>> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                match self
+                    .collection
+                    .find_with_session(filter, options, &mut session)
+                    .await
+                {
+                    Ok(mut cursor) => cursor.stream(&mut session).try_collect().await,
+                    Err(e) => Err(e),
+                }
+            }
+            None => match self.collection.find(filter, options).await {
+                Ok(cursor) => cursor.try_collect().await,
+                Err(e) => Err(e),
+            },
+        };
+        self.track(result)
+    }
+
+    pub async fn count_documents(
+        &self,
+        filter: Document,
+        options: impl Into<Option<CountOptions>>,
+    ) -> Result<u64> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                self.collection
+                    .count_documents_with_session(filter, options, &mut session)
+                    .await
+            }
+            None => self.collection.count_documents(filter, options).await,
+        };
+        self.track(result)
+    }
+
+    /// Reads the count from collection metadata instead of scanning. Not
+    /// allowed in a transaction, so it always runs outside the session.
+    pub async fn estimated_document_count(&self) -> Result<u64> {
+        let result = self.collection.estimated_document_count(None).await;
+        self.track(result)
+    }
+
+    pub async fn update_one(
+        &self,
+        filter: Document,
+        update: Document,
+        options: impl Into<Option<UpdateOptions>>,
+    ) -> Result<UpdateResult> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                self.collection
+                    .update_one_with_session(filter, update, options, &mut session)
+                    .await
+            }
+            None => self.collection.update_one(filter, update, options).await,
+        };
+        self.track(result)
+    }
+
+    pub async fn find_one_and_update(
+        &self,
+        filter: Document,
+        update: Document,
+        options: impl Into<Option<FindOneAndUpdateOptions>>,
+    ) -> Result<Option<T>> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                self.collection
+                    .find_one_and_update_with_session(filter, update, options, &mut session)
+                    .await
+            }
+            None => {
+                self.collection
+                    .find_one_and_update(filter, update, options)
+                    .await
+            }
+        };
+        self.track(result)
+    }
+
+    pub async fn delete_one(
+        &self,
+        filter: Document,
+        options: impl Into<Option<DeleteOptions>>,
+    ) -> Result<DeleteResult> {
+        let result = match &self.session {
+            Some(session) => {
+                let mut session = session.lock().await;
+                self.collection
+                    .delete_one_with_session(filter, options, &mut session)
+                    .await
+            }
+            None => self.collection.delete_one(filter, options).await,
+        };
+        self.track(result)
+    }
+
+    fn track<R>(&self, result: Result<R>) -> Result<R> {
+        if let (Err(error), Some(session)) = (&result, &self.session) {
+            session.record(error);
+        }
+        result
+    }
+}
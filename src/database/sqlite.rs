@@ -0,0 +1,402 @@
+use async_trait::async_trait;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedMutexGuard;
+
+use crate::courses::infrastructure::persistence::sqlite_repository::SqliteCourseRepository;
+use crate::database::DatabaseError;
+use crate::pagination::{Cursor, SortDirection};
+use crate::pills::infrastructure::persistense::sqlite_repository::SqlitePillRepository;
+use crate::unit_of_work::{Repositories, Transaction, UnitOfWork};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS pills (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        content TEXT NOT NULL,
+        version INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS idx_pills_title ON pills (title);
+
+    CREATE TABLE IF NOT EXISTS courses (
+        id TEXT PRIMARY KEY,
+        title TEXT NOT NULL,
+        description TEXT NOT NULL,
+        instructor TEXT NOT NULL,
+        difficulty TEXT NOT NULL,
+        hours INTEGER NOT NULL,
+        tags TEXT NOT NULL,
+        price REAL NOT NULL,
+        version INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS idx_courses_title ON courses (title);
+    CREATE INDEX IF NOT EXISTS idx_courses_instructor ON courses (instructor);
+
+    CREATE TABLE IF NOT EXISTS course_sections (
+        course_id TEXT NOT NULL REFERENCES courses (id) ON DELETE CASCADE,
+        id TEXT NOT NULL,
+        title TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (course_id, id)
+    );
+
+    CREATE TABLE IF NOT EXISTS course_pills (
+        course_id TEXT NOT NULL REFERENCES courses (id) ON DELETE CASCADE,
+        pill_id TEXT NOT NULL,
+        section_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (course_id, pill_id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_course_pills_pill ON course_pills (pill_id);
+";
+
+/// Columns added after the first release of the schema, as (table, column,
+/// definition). `CREATE TABLE IF NOT EXISTS` leaves older tables untouched.
+///
+/// This is the only way SQLite schema changes are applied: the migrations in
+/// `database::migrations` and `--migrations-status` cover MongoDB alone, so a
+/// change to the SQLite schema belongs here, added at startup.
+const ADDED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("pills", "version", "INTEGER NOT NULL DEFAULT 0"),
+    ("courses", "version", "INTEGER NOT NULL DEFAULT 0"),
+];
+
+fn add_missing_columns(connection: &Connection) -> Result<(), rusqlite::Error> {
+    for (table, column, definition) in ADDED_COLUMNS {
+        let exists: bool = connection.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+            (table, column),
+            |row| row.get(0),
+        )?;
+        if !exists {
+            connection.execute_batch(&format!(
+                "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+            ))?;
+            tracing::info!("Database: Added column {}.{}", table, column);
+        }
+    }
+    Ok(())
+}
+
+/// A shared SQLite connection. rusqlite is blocking, so every statement runs
+/// on tokio's blocking thread pool through [`SqliteDatabase::call`].
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    connection: Arc<Mutex<Connection>>,
+    /// Held by a [`SqliteUnitOfWork`] transaction from `BEGIN` to `COMMIT` or
+    /// `ROLLBACK`, so statements from other tasks don't join it.
+    transaction_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Set on the handle a transaction's repositories use: their statements
+    /// run under the lock the transaction already holds.
+    in_transaction: bool,
+    path: String,
+}
+
+impl SqliteDatabase {
+    pub fn open(path: &str) -> Result<Self, DatabaseError> {
+        let connection = Connection::open(path)?;
+        connection.pragma_update(None, "foreign_keys", "ON")?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            transaction_lock: Arc::new(tokio::sync::Mutex::new(())),
+            in_transaction: false,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Runs `f` with exclusive access to the connection on a blocking thread,
+    /// waiting for any transaction of another task to finish first.
+    pub async fn call<F, T>(&self, f: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, rusqlite::Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        let _transaction = match self.in_transaction {
+            true => None,
+            false => Some(self.transaction_lock.lock().await),
+        };
+        let connection = self.connection.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection
+                .lock()
+                .map_err(|_| DatabaseError::operation_failed("SQLite connection lock poisoned"))?;
+            f(&mut connection).map_err(DatabaseError::from)
+        })
+        .await
+        .map_err(|e| DatabaseError::operation_failed(format!("SQLite task failed: {}", e)))?
+    }
+}
+
+/// Runs units of work in SQLite transactions on the shared connection. A
+/// transaction holds the connection for its whole duration, so other requests
+/// wait rather than interleave their statements with it.
+pub struct SqliteUnitOfWork {
+    database: SqliteDatabase,
+}
+
+impl SqliteUnitOfWork {
+    pub fn new(database: &SqliteDatabase) -> Self {
+        Self {
+            database: database.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for SqliteUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, DatabaseError> {
+        let lock = self.database.transaction_lock.clone().lock_owned().await;
+        let database = SqliteDatabase {
+            in_transaction: true,
+            ..self.database.clone()
+        };
+
+        database
+            .call(|connection| connection.execute_batch("BEGIN IMMEDIATE"))
+            .await?;
+
+        Ok(Box::new(SqliteTransaction {
+            repositories: Repositories {
+                pill_repository: Arc::new(SqlitePillRepository::new(&database)),
+                course_repository: Arc::new(SqliteCourseRepository::new(&database)),
+            },
+            database,
+            lock: Mutex::new(Some(lock)),
+        }))
+    }
+}
+
+struct SqliteTransaction {
+    repositories: Repositories,
+    database: SqliteDatabase,
+    /// Released once the transaction has been committed or rolled back.
+    lock: Mutex<Option<OwnedMutexGuard<()>>>,
+}
+
+impl SqliteTransaction {
+    /// Rolls back whatever is still open, e.g. after a failed `COMMIT`.
+    async fn rollback(&self) {
+        let result = self
+            .database
+            .call(|connection| match connection.is_autocommit() {
+                true => Ok(()),
+                false => connection.execute_batch("ROLLBACK"),
+            })
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("SQLite: Failed to roll back transaction: {}", e);
+        }
+    }
+
+    fn release(&self) {
+        if let Ok(mut lock) = self.lock.lock() {
+            lock.take();
+        }
+    }
+}
+
+#[async_trait]
+impl Transaction for SqliteTransaction {
+    fn repositories(&self) -> Repositories {
+        self.repositories.clone()
+    }
+
+    fn has_transient_error(&self) -> bool {
+        false
+    }
+
+    async fn commit(&self) -> Result<(), DatabaseError> {
+        let result = self
+            .database
+            .call(|connection| connection.execute_batch("COMMIT"))
+            .await;
+        if let Err(e) = &result {
+            tracing::error!("SQLite: Failed to commit transaction: {}", e);
+            self.rollback().await;
+        }
+        self.release();
+        result
+    }
+
+    async fn abort(&self) {
+        self.rollback().await;
+        self.release();
+    }
+}
+
+impl Drop for SqliteTransaction {
+    /// A transaction dropped before it finished, e.g. because its request was
+    /// cancelled, is rolled back before other tasks may use the connection.
+    fn drop(&mut self) {
+        let Some(lock) = self.lock.get_mut().ok().and_then(Option::take) else {
+            return;
+        };
+        let connection = self.database.connection.clone();
+        let rollback = move || {
+            let _lock = lock;
+            if let Ok(connection) = connection.lock() {
+                if !connection.is_autocommit() {
+                    if let Err(e) = connection.execute_batch("ROLLBACK") {
+                        tracing::warn!("SQLite: Failed to roll back abandoned transaction: {}", e);
+                    }
+                }
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => drop(runtime.spawn_blocking(rollback)),
+            Err(_) => rollback(),
+        }
+    }
+}
+
+/// SQLite counterpart of `DatabaseConfig`, used when `STORAGE_BACKEND=sqlite`.
+pub struct SqliteConfig {
+    pub database: SqliteDatabase,
+}
+
+impl SqliteConfig {
+    pub fn new(path: &str) -> Result<Self, DatabaseError> {
+        let database = SqliteDatabase::open(path)?;
+        tracing::info!("Database: Opened SQLite database at {}", path);
+
+        Ok(SqliteConfig { database })
+    }
+
+    pub fn get_database(&self) -> &SqliteDatabase {
+        &self.database
+    }
+
+    pub async fn initialize_schema(&self) -> Result<(), DatabaseError> {
+        self.database
+            .call(|connection| {
+                connection.execute_batch(SCHEMA)?;
+                add_missing_columns(connection)
+            })
+            .await?;
+
+        tracing::info!("Database: SQLite schema created");
+        Ok(())
+    }
+}
+
+/// `ORDER BY` clause for a page, using `id` as a tie-breaker so the order is total.
+pub fn order_by(column: &str, direction: SortDirection) -> String {
+    let order = match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    format!("ORDER BY {column} {order}, id {order}")
+}
+
+/// Condition selecting the rows that come after a cursor in the given order. It
+/// takes three parameters: the cursor value twice, then the cursor ID.
+pub fn after_cursor(column: &str, direction: SortDirection) -> String {
+    let comparison = match direction {
+        SortDirection::Asc => ">",
+        SortDirection::Desc => "<",
+    };
+    format!("({column} {comparison} ? OR ({column} = ? AND id {comparison} ?))")
+}
+
+/// The cursor value as an SQLite parameter. Returns `None` for values that no
+/// sortable column can hold.
+pub fn cursor_value(cursor: &Cursor) -> Option<SqlValue> {
+    match &cursor.value {
+        serde_json::Value::String(s) => Some(SqlValue::Text(s.clone())),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(SqlValue::Integer(i)),
+            None => n.as_f64().map(SqlValue::Real),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pagination::PageRequest;
+    use crate::pills::domain::{Pill, PillFilter, PillId, PillSort};
+
+    async fn unit_of_work() -> Arc<dyn UnitOfWork> {
+        let config = SqliteConfig::new(":memory:").unwrap();
+        config.initialize_schema().await.unwrap();
+        Arc::new(SqliteUnitOfWork::new(config.get_database()))
+    }
+
+    fn pill(title: &str) -> Pill {
+        Pill::create(PillId::new(), title.into(), "Content".into())
+    }
+
+    async fn stored_titles(unit_of_work: &Arc<dyn UnitOfWork>) -> Vec<String> {
+        let transaction = unit_of_work.begin().await.unwrap();
+        let pills = transaction
+            .repositories()
+            .pill_repository
+            .find_page(
+                &PillFilter::default(),
+                PillSort::default(),
+                &PageRequest::new(None, None).unwrap(),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        pills.items.iter().map(|p| p.title().to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn committed_work_is_kept() {
+        let unit_of_work = unit_of_work().await;
+
+        let result: Result<(), DatabaseError> = unit_of_work
+            .run(|repositories| async move {
+                let mut pill = pill("Kept");
+                repositories.pill_repository.save(&mut pill).await.unwrap();
+                Ok(())
+            })
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(stored_titles(&unit_of_work).await, vec!["Kept"]);
+    }
+
+    #[tokio::test]
+    async fn failed_work_is_rolled_back() {
+        let unit_of_work = unit_of_work().await;
+
+        let result: Result<(), DatabaseError> = unit_of_work
+            .run(|repositories| async move {
+                let mut pill = pill("Discarded");
+                repositories.pill_repository.save(&mut pill).await.unwrap();
+                Err(DatabaseError::TransactionFailed)
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(stored_titles(&unit_of_work).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn abandoned_transaction_is_rolled_back() {
+        let unit_of_work = unit_of_work().await;
+
+        let transaction = unit_of_work.begin().await.unwrap();
+        let mut pill = pill("Abandoned");
+        transaction
+            .repositories()
+            .pill_repository
+            .save(&mut pill)
+            .await
+            .unwrap();
+        drop(transaction);
+
+        assert!(stored_titles(&unit_of_work).await.is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+use mongodb::{
+    bson::doc,
+    error::UNKNOWN_TRANSACTION_COMMIT_RESULT,
+    options::{Acknowledgment, ReadConcern, TransactionOptions, WriteConcern},
+    Client, Database,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::error::map_mongodb_error;
+use super::session::MongoSession;
+use super::DatabaseError;
+use crate::courses::infrastructure::persistence::mongodb_repository::MongoDbCourseRepository;
+use crate::pills::infrastructure::persistense::mongodb_repository::MongoDbPillRepository;
+use crate::unit_of_work::{NoopUnitOfWork, Repositories, Transaction, UnitOfWork};
+
+/// How many times a commit is retried when the server can't tell whether it applied.
+const COMMIT_ATTEMPTS: usize = 3;
+
+/// Runs units of work in MongoDB multi-document transactions. These need a
+/// replica set or sharded cluster, so until [`set_transactions_supported`]
+/// enables them, work runs without a transaction against `fallback`.
+///
+/// [`set_transactions_supported`]: MongoUnitOfWork::set_transactions_supported
+pub struct MongoUnitOfWork {
+    client: Client,
+    database: Database,
+    fallback: NoopUnitOfWork,
+    transactions: AtomicBool,
+}
+
+impl MongoUnitOfWork {
+    pub fn new(client: Client, database: Database, fallback: Repositories) -> Self {
+        Self {
+            client,
+            database,
+            fallback: NoopUnitOfWork::new(fallback),
+            transactions: AtomicBool::new(false),
+        }
+    }
+
+    /// Called once the server has been reached; see [`supports_transactions`].
+    pub fn set_transactions_supported(&self, supported: bool) {
+        self.transactions.store(supported, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for MongoUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, DatabaseError> {
+        if !self.transactions.load(Ordering::SeqCst) {
+            return self.fallback.begin().await;
+        }
+
+        let mut session = self
+            .client
+            .start_session(None)
+            .await
+            .map_err(map_mongodb_error)?;
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+        session
+            .start_transaction(options)
+            .await
+            .map_err(map_mongodb_error)?;
+
+        let session = Arc::new(MongoSession::new(session));
+        let repositories = Repositories {
+            pill_repository: Arc::new(MongoDbPillRepository::with_session(
+                &self.database,
+                session.clone(),
+            )),
+            course_repository: Arc::new(MongoDbCourseRepository::with_session(
+                &self.database,
+                session.clone(),
+            )),
+        };
+
+        Ok(Box::new(MongoTransaction {
+            session,
+            repositories,
+        }))
+    }
+}
+
+struct MongoTransaction {
+    session: Arc<MongoSession>,
+    repositories: Repositories,
+}
+
+#[async_trait]
+impl Transaction for MongoTransaction {
+    fn repositories(&self) -> Repositories {
+        self.repositories.clone()
+    }
+
+    fn has_transient_error(&self) -> bool {
+        self.session.has_transient_error()
+    }
+
+    async fn commit(&self) -> Result<(), DatabaseError> {
+        let mut session = self.session.lock().await;
+        let mut attempt = 1;
+
+        loop {
+            match session.commit_transaction().await {
+                Ok(()) => return Ok(()),
+                Err(e)
+                    if e.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT)
+                        && attempt < COMMIT_ATTEMPTS =>
+                {
+                    tracing::warn!(
+                        "MongoDB: Commit result unknown on attempt {}, retrying: {}",
+                        attempt,
+                        e
+                    );
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.session.record(&e);
+                    tracing::error!("MongoDB: Failed to commit transaction: {}", e);
+                    return Err(map_mongodb_error(e));
+                }
+            }
+        }
+    }
+
+    async fn abort(&self) {
+        let mut session = self.session.lock().await;
+        if let Err(e) = session.abort_transaction().await {
+            tracing::warn!("MongoDB: Failed to abort transaction: {}", e);
+        }
+    }
+}
+
+/// Whether the server accepts multi-document transactions, which standalone
+/// servers don't: only replica set members and `mongos` routers do.
+pub async fn supports_transactions(database: &Database) -> bool {
+    match database.run_command(doc! { "hello": 1 }, None).await {
+        Ok(reply) => reply.get_str("setName").is_ok() || reply.get_str("msg") == Ok("isdbgrid"),
+        Err(e) => {
+            tracing::warn!("MongoDB: Failed to check transaction support: {}", e);
+            false
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use mongodb::bson::{doc, Bson, Document};
+
+/// Selects the document with `id` only while it is still at `expected_version`.
+/// Documents stored before versioning have no `version` field and count as 0.
+pub fn version_filter(id: String, expected_version: u64) -> Document {
+    let version = if expected_version == 0 {
+        doc! { "$in": [0_i64, Bson::Null] }
+    } else {
+        doc! { "$eq": expected_version as i64 }
+    };
+
+    doc! { "_id": id, "version": version }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_zero_also_matches_documents_without_a_version() {
+        let filter = version_filter("p1".to_string(), 0);
+
+        assert_eq!(
+            filter,
+            doc! { "_id": "p1", "version": { "$in": [0_i64, Bson::Null] } }
+        );
+    }
+
+    #[test]
+    fn later_versions_match_exactly() {
+        let filter = version_filter("p1".to_string(), 4);
+
+        assert_eq!(filter, doc! { "_id": "p1", "version": { "$eq": 4_i64 } });
+    }
+}
@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use mongodb::Database;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::database::connector::ConnectionState;
+use crate::database::indexes::EXPECTED_INDEXES;
+use crate::database::PoolMonitor;
+use crate::health::{CheckFailure, DatabaseProbe, HealthCheck};
+
+/// Share of the pool in use above which the pool is reported degraded.
+const POOL_DEGRADED_RATIO: f64 = 0.9;
+
+/// Free space below which SQLite writes are about to fail.
+const DISK_UNHEALTHY_FREE_BYTES: u64 = 100 * 1024 * 1024;
+/// Share of the disk free below which it is reported degraded.
+const DISK_DEGRADED_FREE_RATIO: f64 = 0.1;
+
+/// Pings the storage backend; the service can't work without it.
+pub struct DatabaseCheck {
+    probe: Arc<dyn DatabaseProbe>,
+}
+
+impl DatabaseCheck {
+    pub fn new(probe: Arc<dyn DatabaseProbe>) -> Self {
+        Self { probe }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    fn critical(&self) -> bool {
+        true
+    }
+
+    async fn check(&self) -> Result<Option<String>, CheckFailure> {
+        self.probe
+            .ping()
+            .await
+            .map(|_| Some(self.probe.name()))
+            .map_err(|e| CheckFailure::unhealthy(format!("{}: {}", self.probe.name(), e)))
+    }
+}
+
+/// Reports MongoDB indexes that `create_all_indexes` should have created.
+/// Queries still work without them, only slower.
+pub struct IndexCheck {
+    database: Database,
+    connection: ConnectionState,
+}
+
+impl IndexCheck {
+    pub fn new(database: Database, connection: ConnectionState) -> Self {
+        Self {
+            database,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for IndexCheck {
+    fn name(&self) -> &'static str {
+        "indexes"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Result<Option<String>, CheckFailure> {
+        if !self.connection.is_ready() {
+            return Err(CheckFailure::degraded(
+                "not checked, MongoDB not connected yet",
+            ));
+        }
+
+        let mut missing = Vec::new();
+        for (collection, expected) in EXPECTED_INDEXES {
+            let existing = self
+                .database
+                .collection::<mongodb::bson::Document>(collection)
+                .list_index_names()
+                .await
+                .map_err(|e| {
+                    CheckFailure::degraded(format!("Failed to list {} indexes: {}", collection, e))
+                })?;
+
+            missing.extend(
+                expected
+                    .iter()
+                    .filter(|name| !existing.iter().any(|e| e == *name))
+                    .map(|name| format!("{}.{}", collection, name)),
+            );
+        }
+
+        if missing.is_empty() {
+            Ok(None)
+        } else {
+            Err(CheckFailure::degraded(format!(
+                "missing indexes: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+}
+
+/// Reports when nearly every MongoDB connection is in use, so requests
+/// start queueing for one.
+pub struct PoolCheck {
+    monitor: PoolMonitor,
+    max_pool_size: u32,
+}
+
+impl PoolCheck {
+    pub fn new(monitor: PoolMonitor, max_pool_size: u32) -> Self {
+        Self {
+            monitor,
+            max_pool_size,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for PoolCheck {
+    fn name(&self) -> &'static str {
+        "connection_pool"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Result<Option<String>, CheckFailure> {
+        let in_use = self.monitor.busiest_server_in_use();
+        let usage = format!("{} of {} connections in use", in_use, self.max_pool_size);
+
+        if in_use as f64 >= self.max_pool_size as f64 * POOL_DEGRADED_RATIO {
+            Err(CheckFailure::degraded(format!("pool saturated, {}", usage)))
+        } else {
+            Ok(Some(usage))
+        }
+    }
+}
+
+/// Free space on the volume holding the SQLite database file.
+pub struct DiskSpaceCheck {
+    directory: PathBuf,
+}
+
+impl DiskSpaceCheck {
+    pub fn new(database_path: &str) -> Self {
+        let directory = match Path::new(database_path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        Self { directory }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DiskSpaceCheck {
+    fn name(&self) -> &'static str {
+        "disk_space"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Result<Option<String>, CheckFailure> {
+        let (free, total) = disk_space(&self.directory).map_err(|e| {
+            CheckFailure::degraded(format!(
+                "Failed to read free space of {}: {}",
+                self.directory.display(),
+                e
+            ))
+        })?;
+
+        let usage = format!(
+            "{} MiB free of {} MiB",
+            free / (1024 * 1024),
+            total / (1024 * 1024)
+        );
+
+        if free < DISK_UNHEALTHY_FREE_BYTES {
+            Err(CheckFailure::unhealthy(format!(
+                "disk almost full, {}",
+                usage
+            )))
+        } else if (free as f64) < total as f64 * DISK_DEGRADED_FREE_RATIO {
+            Err(CheckFailure::degraded(format!("disk space low, {}", usage)))
+        } else {
+            Ok(Some(usage))
+        }
+    }
+}
+
+/// Bytes available to unprivileged users and the volume size.
+#[cfg(unix)]
+fn disk_space(directory: &Path) -> std::io::Result<(u64, u64)> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(directory.as_os_str().as_bytes())?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid C string and `stats` is a writable statvfs.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let block_size = stats.f_frsize as u64;
+    Ok((
+        stats.f_bavail as u64 * block_size,
+        stats.f_blocks as u64 * block_size,
+    ))
+}
+
+#[cfg(not(unix))]
+fn disk_space(_directory: &Path) -> std::io::Result<(u64, u64)> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "disk space checks need a Unix system",
+    ))
+}
@@ -1,96 +1,271 @@
+pub mod checks;
+pub mod probes;
+
+use async_trait::async_trait;
 use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Json},
 };
-use mongodb::Database;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Ordered from best to worst, so the overall state is the maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Healthy,
+    /// Working, but something needs attention; still ready for traffic.
+    Degraded,
+    Unhealthy,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthStatus {
-    pub status: String,
-    pub database: DatabaseStatus,
+    pub status: HealthState,
+    pub components: Vec<ComponentStatus>,
     pub timestamp: String,
     pub version: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DatabaseStatus {
-    pub connected: bool,
-    pub database_name: String,
-    pub ping_response_time_ms: Option<u64>,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub status: HealthState,
+    /// How long this component's check took.
+    pub latency_ms: u64,
+    /// What was checked, e.g. the database name or pool usage.
+    pub details: Option<String>,
     pub error: Option<String>,
 }
 
+/// Why a check failed, and how much that matters.
+#[derive(Debug)]
+pub struct CheckFailure {
+    pub state: HealthState,
+    pub message: String,
+}
+
+impl CheckFailure {
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self {
+            state: HealthState::Degraded,
+            message: message.into(),
+        }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self {
+            state: HealthState::Unhealthy,
+            message: message.into(),
+        }
+    }
+}
+
+/// A component the health service checks. `Ok` may carry details to report.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Whether the service is unusable without this component. Decides the
+    /// state reported when the check times out.
+    fn critical(&self) -> bool;
+    async fn check(&self) -> Result<Option<String>, CheckFailure>;
+}
+
+/// A storage backend the database check can ping.
+#[async_trait]
+pub trait DatabaseProbe: Send + Sync {
+    /// Reported as the database component's details.
+    fn name(&self) -> String;
+    /// Round trip to the backend; the error is reported to callers as is.
+    async fn ping(&self) -> Result<(), String>;
+}
+
+struct Component {
+    check: Arc<dyn HealthCheck>,
+    timeout: Duration,
+}
+
+/// The latest check result, served to the health endpoints.
+#[derive(Debug, Clone)]
+pub struct HealthSnapshot {
+    pub status: HealthStatus,
+    /// Not ready until a check passes, then only after `failure_threshold`
+    /// unhealthy checks in a row, so a single failed ping doesn't pull the
+    /// instance out of the load balancer.
+    pub ready: bool,
+    pub consecutive_failures: u32,
+}
+
 pub struct HealthService {
-    database: Arc<Database>,
+    components: Vec<Component>,
+    failure_threshold: u32,
+    latest: RwLock<Option<HealthSnapshot>>,
+    /// Held while requests that found nothing cached run the first refresh.
+    first_refresh: tokio::sync::Mutex<()>,
+    shutting_down: AtomicBool,
 }
 
 impl HealthService {
-    pub fn new(database: Arc<Database>) -> Self {
-        Self { database }
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            components: Vec::new(),
+            failure_threshold,
+            latest: RwLock::new(None),
+            first_refresh: tokio::sync::Mutex::new(()),
+            shutting_down: AtomicBool::new(false),
+        }
     }
 
-    pub async fn check_health(&self) -> HealthStatus {
-        let start_time = std::time::Instant::now();
-        let database_status = self.check_database().await;
-        let ping_time = if database_status.connected {
-            Some(start_time.elapsed().as_millis() as u64)
-        } else {
-            None
+    /// Adds a component, checked with its own `timeout`.
+    pub fn with_check(mut self, check: Arc<dyn HealthCheck>, timeout: Duration) -> Self {
+        self.components.push(Component { check, timeout });
+        self
+    }
+
+    /// Makes readiness fail from now on, so no new traffic is routed here.
+    pub fn start_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Runs the checks every `interval` in the background, so the endpoints
+    /// serve the cached result instead of checking on each request.
+    pub fn spawn_probing(self: &Arc<Self>, interval: Duration) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                service.refresh().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// The cached result, or a fresh one if no check has finished yet.
+    /// Concurrent first requests share a single refresh.
+    pub async fn latest(&self) -> HealthSnapshot {
+        if let Some(snapshot) = self.cached() {
+            return snapshot;
+        }
+
+        let _first_refresh = self.first_refresh.lock().await;
+        match self.cached() {
+            Some(snapshot) => snapshot,
+            None => self.refresh().await,
+        }
+    }
+
+    fn cached(&self) -> Option<HealthSnapshot> {
+        self.latest.read().unwrap().clone()
+    }
+
+    /// Checks every component now and caches the result.
+    pub async fn refresh(&self) -> HealthSnapshot {
+        let status = self.check_health().await;
+
+        let mut latest = self.latest.write().unwrap();
+        let (was_ready, failures) = match latest.as_ref() {
+            Some(previous) => (previous.ready, previous.consecutive_failures),
+            None => (false, 0),
         };
 
-        let overall_status = if database_status.connected {
-            "healthy"
+        let snapshot = if status.status == HealthState::Unhealthy {
+            let consecutive_failures = failures + 1;
+            HealthSnapshot {
+                ready: was_ready && consecutive_failures < self.failure_threshold,
+                consecutive_failures,
+                status,
+            }
         } else {
-            "unhealthy"
+            HealthSnapshot {
+                ready: true,
+                consecutive_failures: 0,
+                status,
+            }
         };
 
+        if snapshot.ready != was_ready {
+            if snapshot.ready {
+                tracing::info!("Health: Ready");
+            } else {
+                tracing::warn!(
+                    "Health: Not ready after {} failed checks in a row",
+                    snapshot.consecutive_failures
+                );
+            }
+        }
+
+        *latest = Some(snapshot.clone());
+        snapshot
+    }
+
+    /// Checks every component concurrently; the overall state is the worst one.
+    async fn check_health(&self) -> HealthStatus {
+        let components =
+            futures::future::join_all(self.components.iter().map(check_component)).await;
+
+        let status = components
+            .iter()
+            .map(|component| component.status)
+            .max()
+            .unwrap_or(HealthState::Healthy);
+
         HealthStatus {
-            status: overall_status.to_string(),
-            database: DatabaseStatus {
-                connected: database_status.connected,
-                database_name: database_status.database_name,
-                ping_response_time_ms: ping_time,
-                error: database_status.error,
-            },
+            status,
+            components,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         }
     }
+}
 
-    async fn check_database(&self) -> DatabaseStatus {
-        match self
-            .database
-            .run_command(mongodb::bson::doc! {"ping": 1}, None)
-            .await
-        {
-            Ok(_) => DatabaseStatus {
-                connected: true,
-                database_name: self.database.name().to_string(),
-                ping_response_time_ms: None, // Will be set by caller
-                error: None,
-            },
-            Err(e) => DatabaseStatus {
-                connected: false,
-                database_name: self.database.name().to_string(),
-                ping_response_time_ms: None,
-                error: Some(format!("Database connection error: {}", e)),
-            },
+async fn check_component(component: &Component) -> ComponentStatus {
+    let check = &component.check;
+    let started = Instant::now();
+    let result = tokio::time::timeout(component.timeout, check.check()).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (status, details, error) = match result {
+        Ok(Ok(details)) => (HealthState::Healthy, details, None),
+        Ok(Err(failure)) => (failure.state, None, Some(failure.message)),
+        Err(_) => {
+            let state = if check.critical() {
+                HealthState::Unhealthy
+            } else {
+                HealthState::Degraded
+            };
+            let message = format!("no response within {}ms", component.timeout.as_millis());
+            (state, None, Some(message))
         }
+    };
+
+    if let Some(error) = &error {
+        tracing::warn!("Health: {} is {:?}: {}", check.name(), status, error);
+    }
+
+    ComponentStatus {
+        name: check.name().to_string(),
+        status,
+        latency_ms,
+        details,
+        error,
     }
 }
 
 pub async fn health_check_handler(
     State(health_service): State<Arc<HealthService>>,
 ) -> impl IntoResponse {
-    let health_status = health_service.check_health().await;
+    let health_status = health_service.latest().await.status;
 
-    let status_code = if health_status.status == "healthy" {
-        StatusCode::OK
-    } else {
+    let status_code = if health_status.status == HealthState::Unhealthy {
         StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
     };
 
     (status_code, Json(health_status)).into_response()
@@ -99,9 +274,22 @@ pub async fn health_check_handler(
 pub async fn readiness_check_handler(
     State(health_service): State<Arc<HealthService>>,
 ) -> impl IntoResponse {
-    let health_status = health_service.check_health().await;
+    if health_service.is_shutting_down() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "not ready",
+                "reason": "shutting down",
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            })),
+        )
+            .into_response();
+    }
 
-    if health_status.database.connected {
+    let snapshot = health_service.latest().await;
+    let health_status = snapshot.status;
+
+    if snapshot.ready {
         (
             StatusCode::OK,
             Json(serde_json::json!({
@@ -111,11 +299,23 @@ pub async fn readiness_check_handler(
         )
             .into_response()
     } else {
+        let failing: Vec<&str> = health_status
+            .components
+            .iter()
+            .filter(|component| component.status == HealthState::Unhealthy)
+            .map(|component| component.name.as_str())
+            .collect();
+        let reason = format!(
+            "unhealthy: {} ({} failed checks in a row)",
+            failing.join(", "),
+            snapshot.consecutive_failures
+        );
+
         (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(serde_json::json!({
                 "status": "not ready",
-                "reason": "database not available",
+                "reason": reason,
                 "timestamp": health_status.timestamp
             })),
         )
@@ -133,3 +333,106 @@ pub async fn liveness_check_handler() -> impl IntoResponse {
     )
         .into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    const THRESHOLD: u32 = 3;
+
+    /// A critical check whose outcome the test controls.
+    #[derive(Default)]
+    struct StubCheck {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl StubCheck {
+        fn set_failing(&self, failing: bool) {
+            self.failing.store(failing, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for StubCheck {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        fn critical(&self) -> bool {
+            true
+        }
+
+        async fn check(&self) -> Result<Option<String>, CheckFailure> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            match self.failing.load(Ordering::SeqCst) {
+                true => Err(CheckFailure::unhealthy("down")),
+                false => Ok(None),
+            }
+        }
+    }
+
+    fn service() -> (Arc<HealthService>, Arc<StubCheck>) {
+        let check = Arc::new(StubCheck::default());
+        let service =
+            HealthService::new(THRESHOLD).with_check(check.clone(), Duration::from_secs(1));
+        (Arc::new(service), check)
+    }
+
+    #[tokio::test]
+    async fn not_ready_until_a_check_passes() {
+        let (service, check) = service();
+        check.set_failing(true);
+
+        assert!(!service.refresh().await.ready);
+
+        check.set_failing(false);
+        assert!(service.refresh().await.ready);
+    }
+
+    #[tokio::test]
+    async fn readiness_flips_at_the_failure_threshold() {
+        let (service, check) = service();
+        assert!(service.refresh().await.ready);
+
+        check.set_failing(true);
+        for failures in 1..THRESHOLD {
+            let snapshot = service.refresh().await;
+            assert!(snapshot.ready, "not ready after {} failures", failures);
+            assert_eq!(snapshot.consecutive_failures, failures);
+        }
+
+        let snapshot = service.refresh().await;
+        assert!(!snapshot.ready);
+        assert_eq!(snapshot.consecutive_failures, THRESHOLD);
+        assert_eq!(snapshot.status.status, HealthState::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn one_success_restores_readiness() {
+        let (service, check) = service();
+        service.refresh().await;
+        check.set_failing(true);
+        for _ in 0..THRESHOLD + 2 {
+            service.refresh().await;
+        }
+        assert!(!service.latest().await.ready);
+
+        check.set_failing(false);
+        let snapshot = service.refresh().await;
+        assert!(snapshot.ready);
+        assert_eq!(snapshot.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_first_requests_run_the_checks_once() {
+        let (service, check) = service();
+
+        let snapshots = futures::future::join_all((0..5).map(|_| service.latest())).await;
+
+        assert!(snapshots.iter().all(|snapshot| snapshot.ready));
+        assert_eq!(check.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use mongodb::Database;
+
+use crate::database::connector::ConnectionState;
+use crate::database::sqlite::SqliteDatabase;
+use crate::health::DatabaseProbe;
+
+/// Reports MongoDB as unavailable until the background connector has
+/// connected and initialized it, then pings it on every check.
+pub struct MongoProbe {
+    database: Database,
+    connection: ConnectionState,
+}
+
+impl MongoProbe {
+    pub fn new(database: Database, connection: ConnectionState) -> Self {
+        Self {
+            database,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl DatabaseProbe for MongoProbe {
+    fn name(&self) -> String {
+        self.database.name().to_string()
+    }
+
+    async fn ping(&self) -> Result<(), String> {
+        if !self.connection.is_ready() {
+            return Err(match self.connection.last_error() {
+                Some(error) => format!("not connected yet, last attempt failed: {}", error),
+                None => "not connected yet".to_string(),
+            });
+        }
+
+        self.database
+            .run_command(mongodb::bson::doc! {"ping": 1}, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl DatabaseProbe for SqliteDatabase {
+    fn name(&self) -> String {
+        format!("sqlite:{}", self.path())
+    }
+
+    async fn ping(&self) -> Result<(), String> {
+        self.call(|connection| connection.query_row("SELECT 1", [], |_| Ok(())))
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// The in-memory backend lives in the process, so it is always reachable.
+pub struct InMemoryProbe;
+
+#[async_trait]
+impl DatabaseProbe for InMemoryProbe {
+    fn name(&self) -> String {
+        "memory".to_string()
+    }
+
+    async fn ping(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
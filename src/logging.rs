@@ -1,35 +1,86 @@
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use std::str::FromStr;
+use tracing_subscriber::{
+    fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer, Registry,
+};
 
-/// Initialize the tracing subscriber for logging
-pub fn init() {
-    // Set default log level to INFO if RUST_LOG is not set
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+use crate::config::LoggingSettings;
 
-    tracing_subscriber::registry()
-        .with(filter)
-        .with(
-            fmt::layer()
-                .with_target(true)
-                .with_thread_ids(true)
-                .with_file(true)
-                .with_line_number(true),
-        )
-        .init();
+/// How log lines are written, selected with `LOG_FORMAT` or `logging.format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable, with timestamp, level, target, thread, file and line.
+    #[default]
+    Pretty,
+    /// Human-readable and shorter: no thread, file or line.
+    Compact,
+    /// One JSON object per line, for log aggregation in production.
+    Json,
 }
 
-/// Initialize structured JSON logging for production
-pub fn init_json() {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format '{}', expected 'pretty', 'compact' or 'json'",
+                other
+            )),
+        }
+    }
+}
+
+/// Changes the `EnvFilter` of the running subscriber, e.g. to turn on `debug`
+/// for one module during an incident without restarting.
+#[derive(Clone)]
+pub struct LogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogFilter {
+    /// The directives currently in effect, such as `info,rust_ai_pills_blog::database=debug`.
+    pub fn directives(&self) -> String {
+        self.handle
+            .with_current(|filter| filter.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the directives. Invalid ones are rejected and leave the
+    /// current filter in place.
+    pub fn set(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        self.handle.reload(filter).map_err(|e| e.to_string())
+    }
+}
+
+/// Initialize the tracing subscriber for logging in the configured format
+pub fn init(settings: &LoggingSettings) -> LogFilter {
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(&settings.level));
+
+    let format = match settings.format {
+        LogFormat::Pretty => fmt::layer()
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true)
+            .boxed(),
+        LogFormat::Compact => fmt::layer().compact().with_target(true).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true)
+            .boxed(),
+    };
 
     tracing_subscriber::registry()
         .with(filter)
-        .with(
-            fmt::layer()
-                .json()
-                .with_target(true)
-                .with_thread_ids(true)
-                .with_file(true)
-                .with_line_number(true),
-        )
+        .with(format)
         .init();
+
+    LogFilter { handle }
 }
@@ -1,40 +1,77 @@
+mod admin;
+mod api;
+mod cli;
+mod config;
 mod courses;
 mod database;
 mod health;
 mod logging;
+mod metrics;
+mod pagination;
 mod pills;
+mod search;
+mod shutdown;
+mod storage;
+mod unit_of_work;
+mod validation;
 
 use axum::{
-    routing::{get, post},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 use tokio::net::TcpListener;
 
-use pills::application::command::CreatePillCommandHandler;
+use pills::application::command::{
+    CreatePillCommandHandler, DeletePillCommandHandler, UpdatePillCommandHandler,
+};
 use pills::application::query::{FindAllPillsQueryHandler, FindPillQueryHandler};
-use pills::domain::PillRepository;
 use pills::infrastructure::controllers::create_pill_controller::create_pill_controller;
+use pills::infrastructure::controllers::delete_pill_controller::delete_pill_controller;
 use pills::infrastructure::controllers::find_all_pills_controller::find_all_pills_controller;
 use pills::infrastructure::controllers::find_pill_controller::find_pill_by_id_controller;
-use pills::infrastructure::persistense::mongodb_repository::MongoDbPillRepository;
+use pills::infrastructure::controllers::update_pill_controller::{
+    patch_pill_controller, update_pill_controller,
+};
 
-use courses::application::command::{AddPillToCourseCommandHandler, CreateCourseCommandHandler};
+use courses::application::command::{
+    AddPillToCourseCommandHandler, CreateCourseCommandHandler, CreateSectionCommandHandler,
+    DeleteSectionCommandHandler, MovePillToSectionCommandHandler,
+    RemovePillFromCourseCommandHandler, RenameSectionCommandHandler,
+    ReorderCoursePillsCommandHandler, ReorderSectionsCommandHandler, UpdateCourseCommandHandler,
+};
 use courses::application::query::{
     FindAllCoursesQueryHandler, FindCourseQueryHandler, FindCourseWithPillsQueryHandler,
 };
-use courses::domain::CourseRepository;
 use courses::infrastructure::controllers::add_pill_to_course_controller::add_pill_to_course_controller;
 use courses::infrastructure::controllers::create_course_controller::create_course_controller;
+use courses::infrastructure::controllers::create_section_controller::create_section_controller;
+use courses::infrastructure::controllers::delete_section_controller::delete_section_controller;
 use courses::infrastructure::controllers::find_all_courses_controller::find_all_courses_constroller;
 use courses::infrastructure::controllers::find_course_controller::find_course_by_id_controller;
 use courses::infrastructure::controllers::find_course_with_pills_controller::find_course_with_pills_controller;
-use courses::infrastructure::persistence::mongodb_repository::MongoDbCourseRepository;
+use courses::infrastructure::controllers::find_section_controller::find_section_controller;
+use courses::infrastructure::controllers::move_pill_to_section_controller::move_pill_to_section_controller;
+use courses::infrastructure::controllers::remove_pill_from_course_controller::remove_pill_from_course_controller;
+use courses::infrastructure::controllers::rename_section_controller::rename_section_controller;
+use courses::infrastructure::controllers::reorder_course_pills_controller::reorder_course_pills_controller;
+use courses::infrastructure::controllers::reorder_sections_controller::reorder_sections_controller;
+use courses::infrastructure::controllers::update_course_controller::update_course_controller;
+
+use search::application::SearchQueryHandler;
+use search::infrastructure::controllers::search_controller::search_controller;
+
+use admin::{get_log_level_handler, require_admin_token, set_log_level_handler};
+use api::request_id::track_request_id;
+use cli::Command;
+use config::AppConfig;
+use storage::Storage;
 
-use database::DatabaseConfig;
 use health::{
     health_check_handler, liveness_check_handler, readiness_check_handler, HealthService,
 };
+use metrics::{metrics_handler, track_requests, Metrics};
 
 #[derive(Clone)]
 struct AppState {
@@ -42,6 +79,8 @@ struct AppState {
     create_pill_handler: Arc<CreatePillCommandHandler>,
     find_pill_handler: Arc<FindPillQueryHandler>,
     find_all_pills_handler: Arc<FindAllPillsQueryHandler>,
+    update_pill_handler: Arc<UpdatePillCommandHandler>,
+    delete_pill_handler: Arc<DeletePillCommandHandler>,
 
     // Courses handlers
     create_course_handler: Arc<CreateCourseCommandHandler>,
@@ -49,9 +88,23 @@ struct AppState {
     find_all_courses_handler: Arc<FindAllCoursesQueryHandler>,
     find_course_with_pills_handler: Arc<FindCourseWithPillsQueryHandler>,
     add_pill_to_course_handler: Arc<AddPillToCourseCommandHandler>,
+    update_course_handler: Arc<UpdateCourseCommandHandler>,
+    remove_pill_from_course_handler: Arc<RemovePillFromCourseCommandHandler>,
+    reorder_course_pills_handler: Arc<ReorderCoursePillsCommandHandler>,
+    create_section_handler: Arc<CreateSectionCommandHandler>,
+    rename_section_handler: Arc<RenameSectionCommandHandler>,
+    reorder_sections_handler: Arc<ReorderSectionsCommandHandler>,
+    delete_section_handler: Arc<DeleteSectionCommandHandler>,
+    move_pill_to_section_handler: Arc<MovePillToSectionCommandHandler>,
+
+    // Search handlers
+    search_handler: Arc<SearchQueryHandler>,
 
     // Health service
     health_service: Arc<HealthService>,
+
+    // Metrics
+    metrics: Arc<Metrics>,
 }
 
 #[tokio::main]
@@ -59,37 +112,61 @@ async fn main() {
     // Load environment variables
     dotenv::dotenv().ok();
 
-    // Initialize logging
-    logging::init();
+    let command = match Command::from_args(std::env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
 
-    // Initialize database connection
-    let db_config = DatabaseConfig::new()
-        .await
-        .expect("Failed to connect to MongoDB");
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // Initialize logging
+    let log_filter = logging::init(&config.logging);
+    tracing::info!(
+        "Starting {} ({:?})",
+        config.app.name,
+        config.app.environment
+    );
 
-    // Create database indexes for better performance
-    if let Err(e) = db_config.initialize_indexes().await {
-        tracing::warn!("Failed to create database indexes: {}", e);
-        tracing::warn!("Application will continue but performance may be affected");
+    // Initialize storage
+    if let Command::Migrations(migration_command) = command {
+        std::process::exit(migration_command.run(&config.database).await);
     }
 
-    let database = db_config.get_database();
+    tracing::info!("Storage backend: {:?}", config.database.backend);
+    let storage = Storage::init(&config.database, &config.health).await;
+    storage.health_service.spawn_probing(config.health.interval);
 
-    // Initialize health service
-    let health_service = Arc::new(HealthService::new(Arc::new(database.clone())));
+    let health_service = storage.health_service.clone();
 
     // Initialize repositories
-    let pill_repo: Arc<dyn PillRepository> = Arc::new(MongoDbPillRepository::new(database));
-    let course_repo: Arc<dyn CourseRepository> = Arc::new(MongoDbCourseRepository::new(database));
+    let pill_repo = storage.pill_repository.clone();
+    let course_repo = storage.course_repository.clone();
+    let search_repo = storage.search_repository.clone();
+    let unit_of_work = storage.unit_of_work.clone();
 
     let app_state = AppState {
         // Pills handlers
         create_pill_handler: Arc::new(CreatePillCommandHandler::new(pill_repo.clone())),
         find_pill_handler: Arc::new(FindPillQueryHandler::new(pill_repo.clone())),
         find_all_pills_handler: Arc::new(FindAllPillsQueryHandler::new(pill_repo.clone())),
+        update_pill_handler: Arc::new(UpdatePillCommandHandler::new(pill_repo.clone())),
+        delete_pill_handler: Arc::new(DeletePillCommandHandler::new(unit_of_work.clone())),
 
         // Courses handlers
-        create_course_handler: Arc::new(CreateCourseCommandHandler::new(course_repo.clone())),
+        create_course_handler: Arc::new(CreateCourseCommandHandler::new(unit_of_work.clone())),
         find_course_handler: Arc::new(FindCourseQueryHandler::new(course_repo.clone())),
         find_all_courses_handler: Arc::new(FindAllCoursesQueryHandler::new(course_repo.clone())),
         find_course_with_pills_handler: Arc::new(FindCourseWithPillsQueryHandler::new(
@@ -100,7 +177,23 @@ async fn main() {
             course_repo.clone(),
             pill_repo.clone(),
         )),
+        update_course_handler: Arc::new(UpdateCourseCommandHandler::new(course_repo.clone())),
+        remove_pill_from_course_handler: Arc::new(RemovePillFromCourseCommandHandler::new(
+            course_repo.clone(),
+        )),
+        reorder_course_pills_handler: Arc::new(ReorderCoursePillsCommandHandler::new(
+            course_repo.clone(),
+        )),
+        create_section_handler: Arc::new(CreateSectionCommandHandler::new(course_repo.clone())),
+        rename_section_handler: Arc::new(RenameSectionCommandHandler::new(course_repo.clone())),
+        reorder_sections_handler: Arc::new(ReorderSectionsCommandHandler::new(course_repo.clone())),
+        delete_section_handler: Arc::new(DeleteSectionCommandHandler::new(course_repo.clone())),
+        move_pill_to_section_handler: Arc::new(MovePillToSectionCommandHandler::new(
+            course_repo.clone(),
+        )),
+        search_handler: Arc::new(SearchQueryHandler::new(search_repo)),
         health_service,
+        metrics: storage.metrics.clone(),
     };
 
     // Create separate routers for different handler states
@@ -108,7 +201,9 @@ async fn main() {
         .route("/health", get(health_check_handler))
         .route("/health/ready", get(readiness_check_handler))
         .with_state(app_state.health_service.clone())
-        .route("/health/live", get(liveness_check_handler));
+        .route("/health/live", get(liveness_check_handler))
+        .route("/metrics", get(metrics_handler))
+        .with_state(app_state.metrics.clone());
 
     let pills_router = Router::new()
         .route("/pills", post(create_pill_controller))
@@ -116,7 +211,14 @@ async fn main() {
         .route("/pills/:id", get(find_pill_by_id_controller))
         .with_state(app_state.find_pill_handler.clone())
         .route("/pills", get(find_all_pills_controller))
-        .with_state(app_state.find_all_pills_handler.clone());
+        .with_state(app_state.find_all_pills_handler.clone())
+        .route(
+            "/pills/:id",
+            put(update_pill_controller).patch(patch_pill_controller),
+        )
+        .with_state(app_state.update_pill_handler.clone())
+        .route("/pills/:id", delete(delete_pill_controller))
+        .with_state(app_state.delete_pill_handler.clone());
 
     let courses_router = Router::new()
         .route("/courses", post(create_course_controller))
@@ -128,32 +230,128 @@ async fn main() {
         .route("/courses/:id/pills", get(find_course_with_pills_controller))
         .with_state(app_state.find_course_with_pills_handler.clone())
         .route("/courses/:id/pills", post(add_pill_to_course_controller))
-        .with_state(app_state.add_pill_to_course_handler.clone());
+        .with_state(app_state.add_pill_to_course_handler.clone())
+        .route("/courses/:id", patch(update_course_controller))
+        .with_state(app_state.update_course_handler.clone())
+        .route(
+            "/courses/:id/pills/:pill_id",
+            delete(remove_pill_from_course_controller),
+        )
+        .with_state(app_state.remove_pill_from_course_handler.clone())
+        .route(
+            "/courses/:id/pills/order",
+            put(reorder_course_pills_controller),
+        )
+        .with_state(app_state.reorder_course_pills_handler.clone())
+        .route(
+            "/courses/:id/pills/:pill_id/section",
+            put(move_pill_to_section_controller),
+        )
+        .with_state(app_state.move_pill_to_section_handler.clone())
+        .route(
+            "/courses/:id/sections/:section_id",
+            get(find_section_controller),
+        )
+        .with_state(app_state.find_course_handler.clone())
+        .route("/courses/:id/sections", post(create_section_controller))
+        .with_state(app_state.create_section_handler.clone())
+        .route(
+            "/courses/:id/sections/order",
+            put(reorder_sections_controller),
+        )
+        .with_state(app_state.reorder_sections_handler.clone())
+        .route(
+            "/courses/:id/sections/:section_id",
+            patch(rename_section_controller),
+        )
+        .with_state(app_state.rename_section_handler.clone())
+        .route(
+            "/courses/:id/sections/:section_id",
+            delete(delete_section_controller),
+        )
+        .with_state(app_state.delete_section_handler.clone());
+
+    let search_router = Router::new()
+        .route("/search", get(search_controller))
+        .with_state(app_state.search_handler.clone());
+
+    let admin_router = match &config.admin.token {
+        Some(token) => Router::new()
+            .route(
+                "/admin/log-level",
+                get(get_log_level_handler).put(set_log_level_handler),
+            )
+            .with_state(log_filter)
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(token.clone()),
+                require_admin_token,
+            )),
+        None => {
+            tracing::info!("Admin endpoints disabled: ADMIN_TOKEN is not set");
+            Router::new()
+        }
+    };
 
     let app = Router::new()
         .merge(health_router)
         .merge(pills_router)
-        .merge(courses_router);
+        .merge(courses_router)
+        .merge(search_router)
+        .merge(admin_router)
+        .layer(middleware::from_fn_with_state(
+            app_state.metrics.clone(),
+            track_requests,
+        ))
+        .layer(middleware::from_fn(track_request_id));
 
-    let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = std::env::var("SERVER_PORT").unwrap_or_else(|_| "3000".to_string());
-    let address = format!("{}:{}", host, port);
+    let address = config.server.address();
 
     tracing::info!("🚀 Server listening on {}", address);
     tracing::info!("📊 Health check available at: http://{}/health", address);
+    tracing::info!("📈 Metrics available at: http://{}/metrics", address);
     tracing::info!("🔍 API endpoints:");
     tracing::info!("   - POST /pills - Create a pill");
     tracing::info!("   - GET  /pills - Get all pills");
     tracing::info!("   - GET  /pills/{{id}} - Get pill by ID");
+    tracing::info!("   - PUT  /pills/{{id}} - Replace a pill");
+    tracing::info!("   - PATCH /pills/{{id}} - Partially update a pill");
+    tracing::info!(
+        "   - DELETE /pills/{{id}} - Delete a pill (?cascade=true to detach from courses)"
+    );
     tracing::info!("   - POST /courses - Create a course");
     tracing::info!("   - GET  /courses - Get all courses");
     tracing::info!("   - GET  /courses/{{id}} - Get course by ID");
+    tracing::info!("   - PATCH /courses/{{id}} - Partially update a course");
     tracing::info!("   - GET  /courses/{{id}}/pills - Get course with pills");
     tracing::info!("   - POST /courses/{{id}}/pills - Add pill to course");
+    tracing::info!("   - DELETE /courses/{{id}}/pills/{{pill_id}} - Remove pill from course");
+    tracing::info!("   - PUT  /courses/{{id}}/pills/order - Reorder course pills");
+    tracing::info!("   - PUT  /courses/{{id}}/pills/{{pill_id}}/section - Move pill to a section");
+    tracing::info!("   - POST /courses/{{id}}/sections - Create a section");
+    tracing::info!("   - GET  /courses/{{id}}/sections/{{section_id}} - Get a section");
+    tracing::info!("   - PUT  /courses/{{id}}/sections/order - Reorder sections");
+    tracing::info!("   - PATCH /courses/{{id}}/sections/{{section_id}} - Rename a section");
+    tracing::info!("   - DELETE /courses/{{id}}/sections/{{section_id}} - Delete an empty section");
+    tracing::info!("   - GET  /search?q= - Full-text search over pills and courses");
     tracing::info!("   - GET  /health - Health check");
     tracing::info!("   - GET  /health/ready - Readiness probe");
     tracing::info!("   - GET  /health/live - Liveness probe");
+    tracing::info!("   - GET  /metrics - Prometheus metrics");
+    if config.admin.token.is_some() {
+        tracing::info!("   - GET  /admin/log-level - Current log filter (admin token)");
+        tracing::info!("   - PUT  /admin/log-level - Change the log filter (admin token)");
+    }
 
     let listener = TcpListener::bind(&address).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    shutdown::serve(
+        listener,
+        app,
+        storage.health_service.clone(),
+        &config.server,
+    )
+    .await
+    .unwrap();
+
+    storage.close().await;
+    tracing::info!("Shutdown: Complete");
 }
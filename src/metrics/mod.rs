@@ -0,0 +1,249 @@
+pub mod repositories;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::courses::domain::CourseRepository;
+use crate::database::PoolMonitor;
+use crate::pills::domain::PillRepository;
+
+/// How long a scrape waits for the stored pill and course counts. When the
+/// database is slow or unreachable the gauges keep their previous values.
+const COUNT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Route label for requests that matched no route, so arbitrary paths don't
+/// each create a new time series.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Prometheus metrics for HTTP requests, repository operations and storage,
+/// served in the text exposition format at `/metrics`.
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    repository_operation_duration: HistogramVec,
+    stored_pills: IntGauge,
+    stored_courses: IntGauge,
+    pool_connections_in_use: IntGaugeVec,
+    pool_max_size: IntGauge,
+    /// Counted on each scrape; the unwrapped repositories, so counting doesn't
+    /// show up as repository operations.
+    pill_repository: Arc<dyn PillRepository>,
+    course_repository: Arc<dyn CourseRepository>,
+    pool: Option<PoolMonitor>,
+}
+
+impl Metrics {
+    pub fn new(
+        pill_repository: Arc<dyn PillRepository>,
+        course_repository: Arc<dyn CourseRepository>,
+    ) -> Self {
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time taken to handle HTTP requests",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let repository_operation_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "repository_operation_duration_seconds",
+                "Time taken by pill and course repository operations",
+            ),
+            &["repository", "operation", "outcome"],
+        )
+        .expect("valid metric");
+        let stored_pills =
+            IntGauge::new("stored_pills", "Pills currently stored").expect("valid metric");
+        let stored_courses =
+            IntGauge::new("stored_courses", "Courses currently stored").expect("valid metric");
+        let pool_connections_in_use = IntGaugeVec::new(
+            Opts::new(
+                "mongodb_pool_connections_in_use",
+                "MongoDB connections checked out of the pool, per server",
+            ),
+            &["server"],
+        )
+        .expect("valid metric");
+        let pool_max_size = IntGauge::new(
+            "mongodb_pool_max_size",
+            "Maximum MongoDB connections per server",
+        )
+        .expect("valid metric");
+
+        let registry = Registry::new();
+        registry
+            .register(Box::new(http_requests.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(repository_operation_duration.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(stored_pills.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(stored_courses.clone()))
+            .expect("metric registered once");
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            repository_operation_duration,
+            stored_pills,
+            stored_courses,
+            pool_connections_in_use,
+            pool_max_size,
+            pill_repository,
+            course_repository,
+            pool: None,
+        }
+    }
+
+    /// Also exposes MongoDB connection pool usage.
+    pub fn with_pool(mut self, monitor: PoolMonitor, max_pool_size: u32) -> Self {
+        self.registry
+            .register(Box::new(self.pool_connections_in_use.clone()))
+            .expect("metric registered once");
+        self.registry
+            .register(Box::new(self.pool_max_size.clone()))
+            .expect("metric registered once");
+        self.pool_max_size.set(max_pool_size.into());
+        self.pool = Some(monitor);
+        self
+    }
+
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_repository_operation(
+        &self,
+        repository: &str,
+        operation: &str,
+        outcome: &str,
+        elapsed: Duration,
+    ) {
+        self.repository_operation_duration
+            .with_label_values(&[repository, operation, outcome])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Updates the gauges read at scrape time and encodes every metric.
+    pub async fn render(&self) -> Result<String, prometheus::Error> {
+        tokio::join!(self.count_pills(), self.count_courses());
+
+        if let Some(monitor) = &self.pool {
+            // Servers that left the topology shouldn't keep reporting their last value.
+            self.pool_connections_in_use.reset();
+            for (server, in_use) in monitor.in_use_per_server() {
+                self.pool_connections_in_use
+                    .with_label_values(&[&server])
+                    .set(in_use as i64);
+            }
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer).expect("the text format is UTF-8"))
+    }
+
+    async fn count_pills(&self) {
+        match tokio::time::timeout(COUNT_TIMEOUT, self.pill_repository.count()).await {
+            Ok(Ok(count)) => self.stored_pills.set(count as i64),
+            Ok(Err(e)) => tracing::warn!("Metrics: Failed to count pills: {}", e),
+            Err(_) => tracing::warn!("Metrics: Counting pills timed out"),
+        }
+    }
+
+    async fn count_courses(&self) {
+        match tokio::time::timeout(COUNT_TIMEOUT, self.course_repository.count()).await {
+            Ok(Ok(count)) => self.stored_courses.set(count as i64),
+            Ok(Err(e)) => tracing::warn!("Metrics: Failed to count courses: {}", e),
+            Err(_) => tracing::warn!("Metrics: Counting courses timed out"),
+        }
+    }
+}
+
+/// Records the count and duration of every request, labelled with the route
+/// template (`/pills/:id`) rather than the concrete path.
+pub async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+
+    metrics.observe_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+pub async fn metrics_handler(State(metrics): State<Arc<Metrics>>) -> Response {
+    match metrics.render().await {
+        Ok(body) => ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response(),
+        Err(e) => {
+            tracing::error!("Metrics: Failed to encode metrics: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::courses::infrastructure::persistence::in_memory_repository::InMemoryCourseRepository;
+    use crate::pills::domain::{Pill, PillId};
+    use crate::pills::infrastructure::persistense::in_memory_repository::InMemoryPillRepository;
+
+    #[tokio::test]
+    async fn render_reports_the_stored_counts() {
+        let pills = Arc::new(InMemoryPillRepository::new());
+        for title in ["First", "Second"] {
+            let mut pill = Pill::create(PillId::new(), title.into(), "Content".into());
+            pills.save(&mut pill).await.unwrap();
+        }
+        let metrics = Metrics::new(pills, Arc::new(InMemoryCourseRepository::new()));
+
+        let output = metrics.render().await.unwrap();
+
+        assert!(output.contains("\nstored_pills 2\n"));
+        assert!(output.contains("\nstored_courses 0\n"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Repository decorators that record the latency and outcome of every
+//! operation in [`Metrics`].
+
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::Metrics;
+use crate::courses::domain::{
+    Course, CourseFilter, CourseId, CourseRepository, CourseRepositoryError, CourseSort,
+};
+use crate::database::DatabaseError;
+use crate::pagination::{Page, PageRequest};
+use crate::pills::domain::{Pill, PillFilter, PillId, PillRepository, PillSort, RepositoryError};
+use crate::unit_of_work::{Repositories, Transaction, UnitOfWork};
+
+/// The `outcome` label of a failed operation: the error variant in snake case.
+trait Outcome {
+    fn outcome(&self) -> &'static str;
+}
+
+impl Outcome for RepositoryError {
+    fn outcome(&self) -> &'static str {
+        match self {
+            RepositoryError::Unexpected => "unexpected",
+            RepositoryError::NotFound => "not_found",
+            RepositoryError::InUse => "in_use",
+            RepositoryError::InvalidCursor => "invalid_cursor",
+            RepositoryError::Invalid(_) => "invalid",
+            RepositoryError::ConcurrencyConflict => "concurrency_conflict",
+        }
+    }
+}
+
+impl Outcome for CourseRepositoryError {
+    fn outcome(&self) -> &'static str {
+        match self {
+            CourseRepositoryError::Unexpected => "unexpected",
+            CourseRepositoryError::NotFound => "not_found",
+            CourseRepositoryError::DuplicateTitle => "duplicate_title",
+            CourseRepositoryError::PillsNotFound(_) => "pills_not_found",
+            CourseRepositoryError::InvalidPillOrder => "invalid_pill_order",
+            CourseRepositoryError::SectionNotFound => "section_not_found",
+            CourseRepositoryError::SectionNotEmpty => "section_not_empty",
+            CourseRepositoryError::InvalidSectionOrder => "invalid_section_order",
+            CourseRepositoryError::InvalidCursor => "invalid_cursor",
+            CourseRepositoryError::Invalid(_) => "invalid",
+            CourseRepositoryError::ConcurrencyConflict => "concurrency_conflict",
+        }
+    }
+}
+
+async fn timed<T, E: Outcome>(
+    metrics: &Metrics,
+    repository: &str,
+    operation: &str,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = future.await;
+    let outcome = match &result {
+        Ok(_) => "ok",
+        Err(e) => e.outcome(),
+    };
+    metrics.observe_repository_operation(repository, operation, outcome, started.elapsed());
+    result
+}
+
+pub struct InstrumentedPillRepository {
+    inner: Arc<dyn PillRepository>,
+    metrics: Arc<Metrics>,
+}
+
+impl InstrumentedPillRepository {
+    pub fn new(inner: Arc<dyn PillRepository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl PillRepository for InstrumentedPillRepository {
+    async fn save(&self, pill: &mut Pill) -> Result<(), RepositoryError> {
+        timed(&self.metrics, "pills", "save", self.inner.save(pill)).await
+    }
+
+    async fn find_by_id(&self, id: PillId) -> Result<Option<Pill>, RepositoryError> {
+        let future = self.inner.find_by_id(id);
+        timed(&self.metrics, "pills", "find_by_id", future).await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &PillFilter,
+        sort: PillSort,
+        page: &PageRequest,
+    ) -> Result<Page<Pill>, RepositoryError> {
+        let future = self.inner.find_page(filter, sort, page);
+        timed(&self.metrics, "pills", "find_page", future).await
+    }
+
+    async fn count(&self) -> Result<u64, RepositoryError> {
+        timed(&self.metrics, "pills", "count", self.inner.count()).await
+    }
+
+    async fn find_by_ids(&self, ids: &[PillId]) -> Result<Vec<Pill>, RepositoryError> {
+        let future = self.inner.find_by_ids(ids);
+        timed(&self.metrics, "pills", "find_by_ids", future).await
+    }
+
+    async fn delete(&self, id: PillId) -> Result<(), RepositoryError> {
+        timed(&self.metrics, "pills", "delete", self.inner.delete(id)).await
+    }
+}
+
+pub struct InstrumentedCourseRepository {
+    inner: Arc<dyn CourseRepository>,
+    metrics: Arc<Metrics>,
+}
+
+impl InstrumentedCourseRepository {
+    pub fn new(inner: Arc<dyn CourseRepository>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl CourseRepository for InstrumentedCourseRepository {
+    async fn save(&self, course: &mut Course) -> Result<(), CourseRepositoryError> {
+        timed(&self.metrics, "courses", "save", self.inner.save(course)).await
+    }
+
+    async fn add_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let future = self.inner.add_pill(course_id, pill_id, expected_version);
+        timed(&self.metrics, "courses", "add_pill", future).await
+    }
+
+    async fn remove_pill(
+        &self,
+        course_id: CourseId,
+        pill_id: PillId,
+        expected_version: Option<u64>,
+    ) -> Result<u64, CourseRepositoryError> {
+        let future = self.inner.remove_pill(course_id, pill_id, expected_version);
+        timed(&self.metrics, "courses", "remove_pill", future).await
+    }
+
+    async fn find_by_id(&self, id: CourseId) -> Result<Option<Course>, CourseRepositoryError> {
+        let future = self.inner.find_by_id(id);
+        timed(&self.metrics, "courses", "find_by_id", future).await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &CourseFilter,
+        sort: CourseSort,
+        page: &PageRequest,
+    ) -> Result<Page<Course>, CourseRepositoryError> {
+        let future = self.inner.find_page(filter, sort, page);
+        timed(&self.metrics, "courses", "find_page", future).await
+    }
+
+    async fn count(&self) -> Result<u64, CourseRepositoryError> {
+        timed(&self.metrics, "courses", "count", self.inner.count()).await
+    }
+
+    async fn find_by_title(&self, title: &str) -> Result<Option<Course>, CourseRepositoryError> {
+        let future = self.inner.find_by_title(title);
+        timed(&self.metrics, "courses", "find_by_title", future).await
+    }
+
+    async fn find_by_pill_id(&self, pill_id: PillId) -> Result<Vec<Course>, CourseRepositoryError> {
+        let future = self.inner.find_by_pill_id(pill_id);
+        timed(&self.metrics, "courses", "find_by_pill_id", future).await
+    }
+}
+
+/// Instruments the repositories of every transaction, which the wrapped unit
+/// of work creates per transaction for MongoDB.
+pub struct InstrumentedUnitOfWork {
+    inner: Arc<dyn UnitOfWork>,
+    metrics: Arc<Metrics>,
+}
+
+impl InstrumentedUnitOfWork {
+    pub fn new(inner: Arc<dyn UnitOfWork>, metrics: Arc<Metrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for InstrumentedUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, DatabaseError> {
+        let inner = self.inner.begin().await?;
+        let repositories = instrument(inner.repositories(), &self.metrics);
+        Ok(Box::new(InstrumentedTransaction {
+            inner,
+            repositories,
+        }))
+    }
+}
+
+struct InstrumentedTransaction {
+    inner: Box<dyn Transaction>,
+    repositories: Repositories,
+}
+
+#[async_trait]
+impl Transaction for InstrumentedTransaction {
+    fn repositories(&self) -> Repositories {
+        self.repositories.clone()
+    }
+
+    fn has_transient_error(&self) -> bool {
+        self.inner.has_transient_error()
+    }
+
+    async fn commit(&self) -> Result<(), DatabaseError> {
+        self.inner.commit().await
+    }
+
+    async fn abort(&self) {
+        self.inner.abort().await
+    }
+}
+
+/// Wraps both repositories so their operations are recorded in `metrics`.
+pub fn instrument(repositories: Repositories, metrics: &Arc<Metrics>) -> Repositories {
+    Repositories {
+        pill_repository: Arc::new(InstrumentedPillRepository::new(
+            repositories.pill_repository,
+            metrics.clone(),
+        )),
+        course_repository: Arc::new(InstrumentedCourseRepository::new(
+            repositories.course_repository,
+            metrics.clone(),
+        )),
+    }
+}
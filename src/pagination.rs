@@ -0,0 +1,271 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::validation::ValidationErrors;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Position after the last item of a page: the value of the sort field and the
+/// ID of that item, used as a tie-breaker. Clients only see it as an opaque string.
+///
+/// It also records the sort it was issued for, since its value can only be
+/// compared with the same field: a price cursor applied to titles would
+/// silently select the wrong items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub sort: String,
+    pub direction: SortDirection,
+    pub value: serde_json::Value,
+    pub id: String,
+}
+
+impl Cursor {
+    pub fn new(sort: &str, direction: SortDirection, value: serde_json::Value, id: String) -> Self {
+        Self {
+            sort: sort.to_string(),
+            direction,
+            value,
+            id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let json = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Decodes a cursor for a request sorted by `sort` in `direction`. Returns
+    /// `None` if it is malformed or was issued for a different sort.
+    pub fn decode_for(encoded: &str, sort: &str, direction: SortDirection) -> Option<Self> {
+        Self::decode(encoded).filter(|cursor| cursor.sort == sort && cursor.direction == direction)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PageRequest {
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+impl PageRequest {
+    pub fn new(limit: Option<u32>, cursor: Option<Cursor>) -> Result<Self, ValidationErrors> {
+        Ok(Self {
+            limit: page_size(limit)?,
+            cursor,
+        })
+    }
+}
+
+/// The requested page size, [`DEFAULT_PAGE_SIZE`] if none was given. Sizes
+/// outside 1 to [`MAX_PAGE_SIZE`] are rejected rather than adjusted, so a
+/// client never gets a different number of items than it asked for.
+pub fn page_size(limit: Option<u32>) -> Result<u32, ValidationErrors> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let mut errors = ValidationErrors::new();
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        errors.add("limit", format!("must be between 1 and {}", MAX_PAGE_SIZE));
+    }
+    errors.into_result()?;
+    Ok(limit)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    /// Number of items matching the filter across all pages.
+    pub total: u64,
+}
+
+/// Pages an in-memory result set with the same order and cursor semantics as
+/// `database::pagination`: by `sort_value`, then by `id` as a tie-breaker.
+/// Items whose sort value can't be compared with the cursor's are skipped, as
+/// MongoDB does for values of a different type.
+pub fn paginate<T>(
+    mut items: Vec<T>,
+    sort: &str,
+    direction: SortDirection,
+    page: &PageRequest,
+    sort_value: impl Fn(&T) -> serde_json::Value,
+    id: impl Fn(&T) -> String,
+) -> Page<T> {
+    let total = items.len() as u64;
+
+    let key = |item: &T| (sort_value(item), id(item));
+    items.sort_by(|a, b| {
+        let (a_value, a_id) = key(a);
+        let (b_value, b_id) = key(b);
+        let ordering = compare_values(&a_value, &b_value)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a_id.cmp(&b_id));
+        match direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    });
+
+    if let Some(cursor) = &page.cursor {
+        let wanted = match direction {
+            SortDirection::Asc => Ordering::Greater,
+            SortDirection::Desc => Ordering::Less,
+        };
+        items.retain(|item| {
+            let (value, item_id) = key(item);
+            match compare_values(&value, &cursor.value) {
+                Some(Ordering::Equal) => item_id.cmp(&cursor.id) == wanted,
+                Some(ordering) => ordering == wanted,
+                None => false,
+            }
+        });
+    }
+
+    let limit = page.limit as usize;
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items
+            .last()
+            .map(|item| Cursor::new(sort, direction, sort_value(item), id(item)).encode())
+    } else {
+        None
+    };
+
+    Page {
+        items,
+        next_cursor,
+        total,
+    }
+}
+
+/// Orders two JSON scalars of the same kind; numbers compare by value and strings bytewise.
+fn compare_values(a: &serde_json::Value, b: &serde_json::Value) -> Option<Ordering> {
+    use serde_json::Value;
+
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ids<'a>(page: &Page<(&'a str, &'a str)>) -> Vec<&'a str> {
+        page.items.iter().map(|(_, id)| *id).collect()
+    }
+
+    #[test]
+    fn cursor_round_trips_through_encoding() {
+        let cursor = Cursor::new("price", SortDirection::Desc, json!(9.5), "abc".to_string());
+
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn cursor_rejects_garbage() {
+        assert_eq!(Cursor::decode("not a cursor"), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode("{}")), None);
+    }
+
+    #[test]
+    fn cursor_is_only_accepted_for_the_sort_it_was_issued_for() {
+        let encoded =
+            Cursor::new("price", SortDirection::Asc, json!(9.5), "a".to_string()).encode();
+
+        assert!(Cursor::decode_for(&encoded, "price", SortDirection::Asc).is_some());
+        assert!(Cursor::decode_for(&encoded, "title", SortDirection::Asc).is_none());
+        assert!(Cursor::decode_for(&encoded, "price", SortDirection::Desc).is_none());
+    }
+
+    #[test]
+    fn page_request_accepts_limits_in_range() {
+        assert_eq!(
+            PageRequest::new(None, None).unwrap().limit,
+            DEFAULT_PAGE_SIZE
+        );
+        assert_eq!(PageRequest::new(Some(1), None).unwrap().limit, 1);
+        assert_eq!(PageRequest::new(Some(50), None).unwrap().limit, 50);
+        assert_eq!(
+            PageRequest::new(Some(MAX_PAGE_SIZE), None).unwrap().limit,
+            MAX_PAGE_SIZE
+        );
+    }
+
+    #[test]
+    fn page_request_rejects_limits_out_of_range() {
+        let mut expected = ValidationErrors::new();
+        expected.add("limit", format!("must be between 1 and {}", MAX_PAGE_SIZE));
+
+        assert_eq!(PageRequest::new(Some(0), None).unwrap_err(), expected);
+        assert_eq!(
+            PageRequest::new(Some(MAX_PAGE_SIZE + 1), None).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn paginate_breaks_ties_by_id_across_pages() {
+        // (sort value, id): three items share the value "b".
+        let items = vec![("b", "3"), ("a", "9"), ("b", "1"), ("b", "2"), ("c", "0")];
+        let page_of = |page: &PageRequest| {
+            paginate(
+                items.clone(),
+                "title",
+                SortDirection::Asc,
+                page,
+                |(value, _)| json!(value),
+                |(_, id)| id.to_string(),
+            )
+        };
+
+        let first = page_of(&PageRequest::new(Some(2), None).unwrap());
+        assert_eq!(ids(&first), vec!["9", "1"]);
+        assert_eq!(first.total, 5);
+
+        let cursor = Cursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(cursor.sort, "title");
+        assert_eq!(cursor.id, "1");
+
+        let second = page_of(&PageRequest::new(Some(2), Some(cursor)).unwrap());
+        assert_eq!(ids(&second), vec!["2", "3"]);
+
+        let cursor = Cursor::decode(second.next_cursor.as_deref().unwrap()).unwrap();
+        let last = page_of(&PageRequest::new(Some(2), Some(cursor)).unwrap());
+        assert_eq!(ids(&last), vec!["0"]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn paginate_descending_breaks_ties_by_id_descending() {
+        let items = vec![("b", "1"), ("b", "2"), ("a", "3")];
+        let page = paginate(
+            items,
+            "title",
+            SortDirection::Desc,
+            &PageRequest::new(Some(10), None).unwrap(),
+            |(value, _)| json!(value),
+            |(_, id)| id.to_string(),
+        );
+
+        assert_eq!(ids(&page), vec!["2", "1", "3"]);
+    }
+}
@@ -1,5 +1,17 @@
+use crate::pills::domain::Pill;
+use crate::validation::ValidationErrors;
+
 #[derive(Debug, Clone)]
 pub struct CreatePillCommand {
     pub title: String,
     pub content: String,
 }
+
+impl CreatePillCommand {
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Pill::validate_title(&self.title, &mut errors);
+        Pill::validate_content(&self.content, &mut errors);
+        errors.into_result()
+    }
+}
@@ -13,10 +13,14 @@ impl CreatePillCommandHandler {
         Self { pills_repository }
     }
 
-    pub async fn handle(&self, command: CreatePillCommand) -> Result<(), RepositoryError> {
+    pub async fn handle(&self, command: CreatePillCommand) -> Result<Pill, RepositoryError> {
+        command.validate()?;
+
         let pill_id = PillId::new();
-        let pill = Pill::create(pill_id, command.title, command.content);
+        let mut pill = Pill::new(pill_id, command.title, command.content)?;
+
+        self.pills_repository.save(&mut pill).await?;
 
-        self.pills_repository.save(&pill).await
+        Ok(pill)
     }
 }
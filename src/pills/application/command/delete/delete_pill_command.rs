@@ -0,0 +1,25 @@
+use crate::pills::domain::PillId;
+
+#[derive(Debug, Clone)]
+pub struct DeletePillCommand {
+    pub id: PillId,
+    /// Remove the pill from every course that references it instead of refusing the delete.
+    pub cascade: bool,
+    /// Version of the pill the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl DeletePillCommand {
+    pub fn new(id: PillId, cascade: bool) -> Self {
+        Self {
+            id,
+            cascade,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use super::delete_pill_command::DeletePillCommand;
+use crate::courses::domain::course_repository::CourseRepositoryError;
+use crate::pills::domain::pills_repository::RepositoryError;
+use crate::unit_of_work::{Repositories, UnitOfWork};
+
+pub struct DeletePillCommandHandler {
+    unit_of_work: Arc<dyn UnitOfWork>,
+}
+
+impl DeletePillCommandHandler {
+    pub fn new(unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        Self { unit_of_work }
+    }
+
+    /// Removing the pill from its courses and deleting it run in one unit of
+    /// work, so a failed cascade doesn't leave courses without the pill while
+    /// the pill itself survives.
+    pub async fn handle(&self, command: DeletePillCommand) -> Result<(), RepositoryError> {
+        self.unit_of_work
+            .run(|repositories| delete(repositories, command.clone()))
+            .await
+    }
+}
+
+async fn delete(
+    repositories: Repositories,
+    command: DeletePillCommand,
+) -> Result<(), RepositoryError> {
+    let pill = repositories
+        .pill_repository
+        .find_by_id(command.id)
+        .await?
+        .ok_or(RepositoryError::NotFound)?;
+
+    if !pill.matches_version(command.expected_version) {
+        return Err(RepositoryError::ConcurrencyConflict);
+    }
+
+    let courses = repositories
+        .course_repository
+        .find_by_pill_id(command.id)
+        .await
+        .map_err(|_| RepositoryError::Unexpected)?;
+
+    if !courses.is_empty() {
+        if !command.cascade {
+            tracing::warn!(
+                "Handler (DeletePill): Pill {} is referenced by {} courses, refusing to delete",
+                command.id,
+                courses.len()
+            );
+            return Err(RepositoryError::InUse);
+        }
+
+        for course in courses {
+            match repositories
+                .course_repository
+                .remove_pill(course.id(), command.id, None)
+                .await
+            {
+                // `NotFound`: a concurrent request already removed it.
+                Ok(_) | Err(CourseRepositoryError::NotFound) => {}
+                Err(CourseRepositoryError::ConcurrencyConflict) => {
+                    return Err(RepositoryError::ConcurrencyConflict)
+                }
+                Err(_) => return Err(RepositoryError::Unexpected),
+            }
+
+            tracing::info!(
+                "Handler (DeletePill): Pill {} removed from course {}",
+                command.id,
+                course.id()
+            );
+        }
+    }
+
+    repositories.pill_repository.delete(command.id).await
+}
@@ -0,0 +1,6 @@
+pub mod delete_pill_command;
+pub mod delete_pill_command_handler;
+
+// Re-export commonly used items
+pub use delete_pill_command::DeletePillCommand;
+pub use delete_pill_command_handler::DeletePillCommandHandler;
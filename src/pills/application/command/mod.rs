@@ -1,4 +1,12 @@
 pub mod create;
+pub mod delete;
+pub mod update;
 
 // Re-export commonly used items from create
 pub use create::{CreatePillCommand, CreatePillCommandHandler};
+
+// Re-export commonly used items from update
+pub use update::{UpdatePillCommand, UpdatePillCommandHandler};
+
+// Re-export commonly used items from delete
+pub use delete::{DeletePillCommand, DeletePillCommandHandler};
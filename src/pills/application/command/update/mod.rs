@@ -0,0 +1,6 @@
+pub mod update_pill_command;
+pub mod update_pill_command_handler;
+
+// Re-export commonly used items
+pub use update_pill_command::UpdatePillCommand;
+pub use update_pill_command_handler::UpdatePillCommandHandler;
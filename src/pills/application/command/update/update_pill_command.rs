@@ -0,0 +1,39 @@
+use crate::pills::domain::{Pill, PillId};
+use crate::validation::ValidationErrors;
+
+#[derive(Debug, Clone)]
+pub struct UpdatePillCommand {
+    pub id: PillId,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    /// Version of the pill the client last saw, from `If-Match`; `None` skips the check.
+    pub expected_version: Option<u64>,
+}
+
+impl UpdatePillCommand {
+    pub fn new(id: PillId, title: Option<String>, content: Option<String>) -> Self {
+        Self {
+            id,
+            title,
+            content,
+            expected_version: None,
+        }
+    }
+
+    pub fn with_expected_version(mut self, expected_version: Option<u64>) -> Self {
+        self.expected_version = expected_version;
+        self
+    }
+
+    /// Checks the fields being changed; omitted fields are not validated.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Some(title) = &self.title {
+            Pill::validate_title(title, &mut errors);
+        }
+        if let Some(content) = &self.content {
+            Pill::validate_content(content, &mut errors);
+        }
+        errors.into_result()
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use super::update_pill_command::UpdatePillCommand;
+use crate::pills::domain::pills_repository::{PillRepository, RepositoryError};
+use crate::pills::domain::Pill;
+
+pub struct UpdatePillCommandHandler {
+    pills_repository: Arc<dyn PillRepository>,
+}
+
+impl UpdatePillCommandHandler {
+    pub fn new(pills_repository: Arc<dyn PillRepository>) -> Self {
+        Self { pills_repository }
+    }
+
+    pub async fn handle(&self, command: UpdatePillCommand) -> Result<Pill, RepositoryError> {
+        command.validate()?;
+
+        let mut pill = self
+            .pills_repository
+            .find_by_id(command.id)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+
+        if !pill.matches_version(command.expected_version) {
+            return Err(RepositoryError::ConcurrencyConflict);
+        }
+
+        if let Some(title) = command.title {
+            pill.update_title(title);
+        }
+
+        if let Some(content) = command.content {
+            pill.update_content(content);
+        }
+
+        self.pills_repository.save(&mut pill).await?;
+
+        tracing::info!("Handler (UpdatePill): Pill {} updated", pill.id());
+        Ok(pill)
+    }
+}
@@ -3,6 +3,8 @@ pub mod query;
 
 // Re-export commonly used items from command
 pub use command::{CreatePillCommand, CreatePillCommandHandler};
+pub use command::{DeletePillCommand, DeletePillCommandHandler};
+pub use command::{UpdatePillCommand, UpdatePillCommandHandler};
 
 // Re-export commonly used items from query
 pub use query::{FindPillQuery, FindPillQueryHandler};
@@ -1,7 +1,12 @@
-use crate::pills::domain::{Pill, PillRepository, RepositoryError};
+use crate::pagination::{Page, PageRequest};
+use crate::pills::domain::{Pill, PillFilter, PillRepository, PillSort, RepositoryError};
 use std::sync::Arc;
 
-pub struct FindAllPillsQuery;
+pub struct FindAllPillsQuery {
+    pub filter: PillFilter,
+    pub sort: PillSort,
+    pub page: PageRequest,
+}
 
 pub struct FindAllPillsQueryHandler {
     repository: Arc<dyn PillRepository>,
@@ -12,8 +17,11 @@ impl FindAllPillsQueryHandler {
         Self { repository }
     }
 
-    pub async fn handle(&self, _query: FindAllPillsQuery) -> Result<Vec<Pill>, RepositoryError> {
-        let pills = self.repository.find_all().await?;
+    pub async fn handle(&self, query: FindAllPillsQuery) -> Result<Page<Pill>, RepositoryError> {
+        let pills = self
+            .repository
+            .find_page(&query.filter, query.sort, &query.page)
+            .await?;
 
         Ok(pills)
     }
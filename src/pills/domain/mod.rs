@@ -3,4 +3,4 @@ pub mod pills_repository;
 
 pub use pill::Pill;
 pub use pill::PillId;
-pub use pills_repository::{PillRepository, RepositoryError};
+pub use pills_repository::{PillFilter, PillRepository, PillSort, PillSortField, RepositoryError};
@@ -3,6 +3,11 @@ use std::fmt;
 use std::str::FromStr;
 use uuid::Uuid;
 
+use crate::validation::ValidationErrors;
+
+pub const MAX_TITLE_LENGTH: usize = 200;
+pub const MAX_CONTENT_LENGTH: usize = 50_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct PillId(#[serde(with = "uuid_as_string")] Uuid);
@@ -59,11 +64,37 @@ pub struct Pill {
     id: PillId,
     title: String,
     content: String,
+    /// Number of times the pill has been saved; 0 until it is first stored.
+    #[serde(default)]
+    version: u64,
 }
 
 impl Pill {
     pub fn create(id: PillId, title: String, content: String) -> Self {
-        Self { id, title, content }
+        Self {
+            id,
+            title,
+            content,
+            version: 0,
+        }
+    }
+
+    /// Creates a new pill, rejecting a blank or oversized title or content.
+    pub fn new(id: PillId, title: String, content: String) -> Result<Self, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        Self::validate_title(&title, &mut errors);
+        Self::validate_content(&content, &mut errors);
+        errors.into_result()?;
+
+        Ok(Self::create(id, title, content))
+    }
+
+    pub fn validate_title(title: &str, errors: &mut ValidationErrors) {
+        errors.check_text("title", title, MAX_TITLE_LENGTH);
+    }
+
+    pub fn validate_content(content: &str, errors: &mut ValidationErrors) {
+        errors.check_text("content", content, MAX_CONTENT_LENGTH);
     }
 
     pub fn id(&self) -> PillId {
@@ -77,4 +108,33 @@ impl Pill {
     pub fn content(&self) -> &str {
         &self.content
     }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Sets the stored version of a pill loaded from a repository.
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Records the version a repository assigned when saving the pill.
+    pub fn set_version(&mut self, version: u64) {
+        self.version = version;
+    }
+
+    /// Whether the pill is still at the version a client last saw; no
+    /// expectation always matches.
+    pub fn matches_version(&self, expected: Option<u64>) -> bool {
+        expected.is_none_or(|expected| expected == self.version)
+    }
+
+    pub fn update_title(&mut self, title: String) {
+        self.title = title;
+    }
+
+    pub fn update_content(&mut self, content: String) {
+        self.content = content;
+    }
 }
@@ -1,7 +1,10 @@
 use async_trait::async_trait;
+use serde::Deserialize;
 use thiserror::Error;
 
+use crate::pagination::{Cursor, Page, PageRequest, SortDirection};
 use crate::pills::domain::{Pill, PillId};
+use crate::validation::ValidationErrors;
 
 #[derive(Error, Debug, Clone)]
 pub enum RepositoryError {
@@ -9,11 +12,81 @@ pub enum RepositoryError {
     Unexpected,
     #[error("Pill not found")]
     NotFound,
+    #[error("Pill is still referenced by one or more courses")]
+    InUse,
+    #[error("Invalid pagination cursor")]
+    InvalidCursor,
+    #[error("Validation failed: {0}")]
+    Invalid(#[from] ValidationErrors),
+    #[error("Pill was modified by another request")]
+    ConcurrencyConflict,
+}
+
+/// Criteria for listing pills. Unset fields don't restrict the result.
+#[derive(Debug, Clone, Default)]
+pub struct PillFilter {
+    pub title_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PillSortField {
+    #[default]
+    Title,
+}
+
+impl PillSortField {
+    /// The name used in query strings and pagination cursors.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PillSortField::Title => "title",
+        }
+    }
+
+    /// Value of this field for `pill`, as stored in a pagination cursor.
+    pub fn value_of(&self, pill: &Pill) -> serde_json::Value {
+        match self {
+            PillSortField::Title => pill.title().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PillSort {
+    pub field: PillSortField,
+    pub direction: SortDirection,
+}
+
+impl PillSort {
+    /// The cursor for the page that follows `pill`.
+    pub fn cursor_after(&self, pill: &Pill) -> Cursor {
+        Cursor::new(
+            self.field.name(),
+            self.direction,
+            self.field.value_of(pill),
+            pill.id().to_string(),
+        )
+    }
 }
 
 #[async_trait]
 pub trait PillRepository: Send + Sync {
-    async fn save(&self, pill: &Pill) -> Result<(), RepositoryError>;
+    /// Stores the pill if the stored copy is still at `pill.version()`, or
+    /// doesn't exist yet for version 0, and advances the version on success.
+    /// Fails with `ConcurrencyConflict` otherwise.
+    async fn save(&self, pill: &mut Pill) -> Result<(), RepositoryError>;
     async fn find_by_id(&self, id: PillId) -> Result<Option<Pill>, RepositoryError>;
-    async fn find_all(&self) -> Result<Vec<Pill>, RepositoryError>;
+    async fn find_page(
+        &self,
+        filter: &PillFilter,
+        sort: PillSort,
+        page: &PageRequest,
+    ) -> Result<Page<Pill>, RepositoryError>;
+    /// The number of stored pills. May be an estimate where an exact count is
+    /// expensive, so it's meant for monitoring rather than pagination.
+    async fn count(&self) -> Result<u64, RepositoryError>;
+    /// Loads the given pills in a single round trip. Unknown IDs are skipped and
+    /// the result is in no particular order.
+    async fn find_by_ids(&self, ids: &[PillId]) -> Result<Vec<Pill>, RepositoryError>;
+    async fn delete(&self, id: PillId) -> Result<(), RepositoryError>;
 }
@@ -1,12 +1,10 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
+use axum::extract::State;
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::{ApiError, ApiJson, Created, ETag, Linked};
 use crate::pills::application::{CreatePillCommand, CreatePillCommandHandler};
+use crate::pills::domain::Pill;
 
 #[derive(Deserialize)]
 pub struct CreatePillRequest {
@@ -16,18 +14,14 @@ pub struct CreatePillRequest {
 
 pub async fn create_pill_controller(
     State(handler): State<Arc<CreatePillCommandHandler>>,
-    Json(payload): Json<CreatePillRequest>,
-) -> impl IntoResponse {
+    ApiJson(payload): ApiJson<CreatePillRequest>,
+) -> Result<(ETag, Created<Pill>), ApiError> {
     let command = CreatePillCommand {
         title: payload.title,
         content: payload.content,
     };
 
-    match handler.handle(command).await {
-        Ok(_) => StatusCode::CREATED,
-        Err(e) => {
-            tracing::error!("Pill Controller: Error creating pill: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        }
-    }
+    let pill = handler.handle(command).await?;
+    let href = format!("/pills/{}", pill.id());
+    Ok((ETag(pill.version()), Created(Linked::new(pill, href))))
 }
@@ -0,0 +1,25 @@
+use axum::{extract::State, http::StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiPath, ApiQuery, IfMatch};
+use crate::pills::application::{DeletePillCommand, DeletePillCommandHandler};
+use crate::pills::domain::PillId;
+
+#[derive(Deserialize)]
+pub struct DeletePillParams {
+    cascade: Option<bool>,
+}
+
+pub async fn delete_pill_controller(
+    State(handler): State<Arc<DeletePillCommandHandler>>,
+    ApiPath(id): ApiPath<PillId>,
+    ApiQuery(params): ApiQuery<DeletePillParams>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<StatusCode, ApiError> {
+    let command = DeletePillCommand::new(id, params.cascade.unwrap_or(false))
+        .with_expected_version(expected_version);
+
+    handler.handle(command).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,19 +1,47 @@
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::{ApiError, ApiQuery};
+use crate::pagination::{Cursor, Page, PageRequest, SortDirection};
 use crate::pills::application::query::{FindAllPillsQuery, FindAllPillsQueryHandler};
+use crate::pills::application::RepositoryError;
+use crate::pills::domain::{Pill, PillFilter, PillSort, PillSortField};
+
+#[derive(Deserialize)]
+pub struct FindAllPillsParams {
+    title_prefix: Option<String>,
+    sort: Option<PillSortField>,
+    order: Option<SortDirection>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
 
 pub async fn find_all_pills_controller(
     State(handler): State<Arc<FindAllPillsQueryHandler>>,
-) -> impl IntoResponse {
-    let query = FindAllPillsQuery;
+    ApiQuery(params): ApiQuery<FindAllPillsParams>,
+) -> Result<Json<Page<Pill>>, ApiError> {
+    let sort = PillSort {
+        field: params.sort.unwrap_or_default(),
+        direction: params.order.unwrap_or_default(),
+    };
+
+    // A cursor only continues the sort it was issued for.
+    let cursor = match params.cursor.as_deref() {
+        Some(encoded) => match Cursor::decode_for(encoded, sort.field.name(), sort.direction) {
+            Some(cursor) => Some(cursor),
+            None => return Err(RepositoryError::InvalidCursor.into()),
+        },
+        None => None,
+    };
+
+    let query = FindAllPillsQuery {
+        filter: PillFilter {
+            title_prefix: params.title_prefix,
+        },
+        sort,
+        page: PageRequest::new(params.limit, cursor)?,
+    };
 
-    match handler.handle(query).await {
-        Ok(pills) => (StatusCode::OK, Json(pills)).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    Ok(Json(handler.handle(query).await?))
 }
@@ -1,22 +1,16 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
-};
+use axum::{extract::State, response::Json};
 use std::sync::Arc;
 
-use crate::pills::application::{FindPillQuery, FindPillQueryHandler, RepositoryError};
-use crate::pills::domain::PillId;
+use crate::api::{ApiError, ApiPath, ETag};
+use crate::pills::application::{FindPillQuery, FindPillQueryHandler};
+use crate::pills::domain::{Pill, PillId};
 
 pub async fn find_pill_by_id_controller(
     State(handler): State<Arc<FindPillQueryHandler>>,
-    Path(id): Path<PillId>,
-) -> impl IntoResponse {
+    ApiPath(id): ApiPath<PillId>,
+) -> Result<(ETag, Json<Pill>), ApiError> {
     let query = FindPillQuery { id };
 
-    match handler.handle(query).await {
-        Ok(pill) => (StatusCode::OK, Json(pill)).into_response(),
-        Err(RepositoryError::NotFound) => StatusCode::NOT_FOUND.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
-    }
+    let pill = handler.handle(query).await?;
+    Ok((ETag(pill.version()), Json(pill)))
 }
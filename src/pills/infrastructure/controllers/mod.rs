@@ -1,3 +1,5 @@
 pub mod create_pill_controller;
+pub mod delete_pill_controller;
 pub mod find_all_pills_controller;
 pub mod find_pill_controller;
+pub mod update_pill_controller;
@@ -0,0 +1,45 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiJson, ApiPath, ETag, IfMatch};
+use crate::pills::application::{UpdatePillCommand, UpdatePillCommandHandler};
+use crate::pills::domain::{Pill, PillId};
+
+#[derive(Deserialize)]
+pub struct UpdatePillRequest {
+    title: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+pub struct PatchPillRequest {
+    title: Option<String>,
+    content: Option<String>,
+}
+
+pub async fn update_pill_controller(
+    State(handler): State<Arc<UpdatePillCommandHandler>>,
+    ApiPath(id): ApiPath<PillId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<UpdatePillRequest>,
+) -> Result<(ETag, Json<Pill>), ApiError> {
+    let command = UpdatePillCommand::new(id, Some(payload.title), Some(payload.content))
+        .with_expected_version(expected_version);
+
+    let pill = handler.handle(command).await?;
+    Ok((ETag(pill.version()), Json(pill)))
+}
+
+pub async fn patch_pill_controller(
+    State(handler): State<Arc<UpdatePillCommandHandler>>,
+    ApiPath(id): ApiPath<PillId>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(payload): ApiJson<PatchPillRequest>,
+) -> Result<(ETag, Json<Pill>), ApiError> {
+    let command = UpdatePillCommand::new(id, payload.title, payload.content)
+        .with_expected_version(expected_version);
+
+    let pill = handler.handle(command).await?;
+    Ok((ETag(pill.version()), Json(pill)))
+}
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::pagination::{paginate, Page, PageRequest};
+use crate::pills::application::{PillRepository, RepositoryError};
+use crate::pills::domain::{Pill, PillFilter, PillId, PillSort};
+
+/// Process-local pill store with the same semantics as `MongoDbPillRepository`:
+/// `save` upserts by ID and pills keep their insertion order. Data is lost on restart.
+#[derive(Default)]
+pub struct InMemoryPillRepository {
+    pills: RwLock<Vec<Pill>>,
+}
+
+impl InMemoryPillRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of every stored pill, in insertion order.
+    pub fn all(&self) -> Result<Vec<Pill>, RepositoryError> {
+        Ok(self.read()?.clone())
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<'_, Vec<Pill>>, RepositoryError> {
+        self.pills.read().map_err(|_| {
+            tracing::error!("Repository: In-memory pill store is poisoned");
+            RepositoryError::Unexpected
+        })
+    }
+
+    fn write(&self) -> Result<RwLockWriteGuard<'_, Vec<Pill>>, RepositoryError> {
+        self.pills.write().map_err(|_| {
+            tracing::error!("Repository: In-memory pill store is poisoned");
+            RepositoryError::Unexpected
+        })
+    }
+}
+
+fn matches(filter: &PillFilter, pill: &Pill) -> bool {
+    filter
+        .title_prefix
+        .as_ref()
+        .is_none_or(|prefix| pill.title().starts_with(prefix.as_str()))
+}
+
+#[async_trait]
+impl PillRepository for InMemoryPillRepository {
+    async fn save(&self, pill: &mut Pill) -> Result<(), RepositoryError> {
+        let mut pills = self.write()?;
+        let expected = pill.version();
+        let existing = pills.iter().position(|p| p.id() == pill.id());
+
+        let stored_version = existing.map_or(0, |index| pills[index].version());
+        if stored_version != expected {
+            tracing::warn!(
+                "Repository: Pill {} is no longer at version {}",
+                pill.id(),
+                expected
+            );
+            return Err(RepositoryError::ConcurrencyConflict);
+        }
+
+        pill.set_version(expected + 1);
+        match existing {
+            Some(index) => pills[index] = pill.clone(),
+            None => pills.push(pill.clone()),
+        }
+
+        tracing::info!(
+            "Repository: Pill {} saved in memory at version {}",
+            pill.id(),
+            pill.version()
+        );
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: PillId) -> Result<Option<Pill>, RepositoryError> {
+        let pill = self.read()?.iter().find(|p| p.id() == id).cloned();
+
+        match &pill {
+            Some(_) => tracing::debug!("Repository: Found pill {}", id),
+            None => tracing::debug!("Repository: Pill {} not found", id),
+        }
+
+        Ok(pill)
+    }
+
+    async fn find_page(
+        &self,
+        filter: &PillFilter,
+        sort: PillSort,
+        page: &PageRequest,
+    ) -> Result<Page<Pill>, RepositoryError> {
+        let pills: Vec<Pill> = self
+            .read()?
+            .iter()
+            .filter(|pill| matches(filter, pill))
+            .cloned()
+            .collect();
+
+        let page = paginate(
+            pills,
+            sort.field.name(),
+            sort.direction,
+            page,
+            |pill| sort.field.value_of(pill),
+            |pill| pill.id().to_string(),
+        );
+
+        tracing::info!(
+            "Repository: Found {} pills (page of {} total)",
+            page.items.len(),
+            page.total
+        );
+        Ok(page)
+    }
+
+    async fn count(&self) -> Result<u64, RepositoryError> {
+        Ok(self.read()?.len() as u64)
+    }
+
+    async fn find_by_ids(&self, ids: &[PillId]) -> Result<Vec<Pill>, RepositoryError> {
+        let pills: Vec<Pill> = self
+            .read()?
+            .iter()
+            .filter(|pill| ids.contains(&pill.id()))
+            .cloned()
+            .collect();
+
+        tracing::debug!(
+            "Repository: Found {} of {} requested pills",
+            pills.len(),
+            ids.len()
+        );
+        Ok(pills)
+    }
+
+    async fn delete(&self, id: PillId) -> Result<(), RepositoryError> {
+        let mut pills = self.write()?;
+        let before = pills.len();
+        pills.retain(|p| p.id() != id);
+
+        if pills.len() == before {
+            tracing::debug!("Repository: Pill {} not found for deletion", id);
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::info!("Repository: Pill {} deleted successfully", id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn second_save_from_the_same_version_conflicts() {
+        let repository = InMemoryPillRepository::new();
+        let mut pill = Pill::create(PillId::new(), "Title".into(), "Content".into());
+        repository.save(&mut pill).await.unwrap();
+
+        let mut first = pill.clone();
+        let mut second = pill.clone();
+        first.update_title("First".into());
+        second.update_title("Second".into());
+
+        repository.save(&mut first).await.unwrap();
+        assert_eq!(first.version(), 2);
+        assert!(matches!(
+            repository.save(&mut second).await,
+            Err(RepositoryError::ConcurrencyConflict)
+        ));
+
+        let stored = repository.find_by_id(pill.id()).await.unwrap().unwrap();
+        assert_eq!(stored.title(), "First");
+        assert_eq!(stored.version(), 2);
+    }
+}
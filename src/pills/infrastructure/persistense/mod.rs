@@ -1 +1,3 @@
+pub mod in_memory_repository;
 pub mod mongodb_repository;
+pub mod sqlite_repository;
@@ -1,10 +1,20 @@
 use async_trait::async_trait;
-use futures::TryStreamExt;
-use mongodb::{bson::doc, Collection, Database};
+use mongodb::{
+    bson::{doc, Document},
+    options::FindOptions,
+    Database,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
+use crate::database::error::map_mongodb_error;
+use crate::database::pagination::{after_cursor, sort_document};
+use crate::database::session::{MongoSession, SessionCollection};
+use crate::database::versioning::version_filter;
+use crate::database::DatabaseError;
+use crate::pagination::{Page, PageRequest};
 use crate::pills::application::{PillRepository, RepositoryError};
-use crate::pills::domain::{Pill, PillId};
+use crate::pills::domain::{Pill, PillFilter, PillId, PillSort, PillSortField};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PillDocument {
@@ -12,6 +22,9 @@ struct PillDocument {
     id: String,
     title: String,
     content: String,
+    // Pills stored before versioning have no `version` field and start at 0.
+    #[serde(default)]
+    version: i64,
 }
 
 impl From<&Pill> for PillDocument {
@@ -20,6 +33,7 @@ impl From<&Pill> for PillDocument {
             id: pill.id().to_string(),
             title: pill.title().to_string(),
             content: pill.content().to_string(),
+            version: pill.version() as i64,
         }
     }
 }
@@ -29,26 +43,73 @@ impl TryFrom<PillDocument> for Pill {
 
     fn try_from(doc: PillDocument) -> Result<Self, Self::Error> {
         let id = doc.id.parse::<PillId>()?;
-        Ok(Pill::create(id, doc.title, doc.content))
+        Ok(Pill::create(id, doc.title, doc.content).with_version(doc.version as u64))
+    }
+}
+
+fn filter_document(filter: &PillFilter) -> Document {
+    let mut query = Document::new();
+
+    // An anchored, case-sensitive regex can be served by the `title` index.
+    if let Some(prefix) = &filter.title_prefix {
+        query.insert(
+            "title",
+            doc! { "$regex": format!("^{}", escape_regex(prefix)) },
+        );
+    }
+
+    query
+}
+
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn sort_key(field: PillSortField) -> &'static str {
+    match field {
+        PillSortField::Title => "title",
     }
 }
 
 pub struct MongoDbPillRepository {
-    collection: Collection<PillDocument>,
+    collection: SessionCollection<PillDocument>,
 }
 
 impl MongoDbPillRepository {
     pub fn new(database: &Database) -> Self {
+        Self::with_optional_session(database, None)
+    }
+
+    /// A repository whose reads and writes all belong to `session`'s transaction.
+    pub fn with_session(database: &Database, session: Arc<MongoSession>) -> Self {
+        Self::with_optional_session(database, Some(session))
+    }
+
+    fn with_optional_session(database: &Database, session: Option<Arc<MongoSession>>) -> Self {
         let collection = database.collection::<PillDocument>("pills");
-        Self { collection }
+        Self {
+            collection: SessionCollection::new(collection, session),
+        }
     }
 }
 
 #[async_trait]
 impl PillRepository for MongoDbPillRepository {
-    async fn save(&self, pill: &Pill) -> Result<(), RepositoryError> {
-        let pill_doc = PillDocument::from(pill);
-        let filter = doc! { "_id": pill.id().to_string() };
+    async fn save(&self, pill: &mut Pill) -> Result<(), RepositoryError> {
+        let expected = pill.version();
+        let mut pill_doc = PillDocument::from(&*pill);
+        pill_doc.version = (expected + 1) as i64;
+
+        // Only a new pill may be inserted; if another writer got there first the
+        // insert hits the `_id` unique index instead.
+        let filter = version_filter(pill.id().to_string(), expected);
         let update = doc! {
             "$set": mongodb::bson::to_document(&pill_doc)
                 .map_err(|e| {
@@ -63,18 +124,32 @@ impl PillRepository for MongoDbPillRepository {
                 filter,
                 update,
                 mongodb::options::UpdateOptions::builder()
-                    .upsert(true)
+                    .upsert(expected == 0)
                     .build(),
             )
             .await
-            .map_err(|e| {
-                tracing::error!("Repository: Failed to save pill {}: {}", pill.id(), e);
-                RepositoryError::Unexpected
+            .map_err(|e| match map_mongodb_error(e) {
+                DatabaseError::DuplicateKey => RepositoryError::ConcurrencyConflict,
+                e => {
+                    tracing::error!("Repository: Failed to save pill {}: {}", pill.id(), e);
+                    RepositoryError::Unexpected
+                }
             })?;
 
+        if result.matched_count == 0 && result.upserted_id.is_none() {
+            tracing::warn!(
+                "Repository: Pill {} is no longer at version {}",
+                pill.id(),
+                expected
+            );
+            return Err(RepositoryError::ConcurrencyConflict);
+        }
+        pill.set_version(expected + 1);
+
         tracing::info!(
-            "Repository: Pill {} saved successfully. Modified: {}, Matched: {}",
+            "Repository: Pill {} saved at version {}. Modified: {}, Matched: {}",
             pill.id(),
+            pill.version(),
             result.modified_count,
             result.matched_count
         );
@@ -106,31 +181,122 @@ impl PillRepository for MongoDbPillRepository {
         Ok(pill)
     }
 
-    async fn find_all(&self) -> Result<Vec<Pill>, RepositoryError> {
-        let cursor = self.collection.find(doc! {}, None).await.map_err(|e| {
-            tracing::error!(
-                "Repository: Failed to create cursor for find_all pills: {}",
-                e
-            );
+    async fn find_page(
+        &self,
+        filter: &PillFilter,
+        sort: PillSort,
+        page: &PageRequest,
+    ) -> Result<Page<Pill>, RepositoryError> {
+        let query = filter_document(filter);
+        let sort_key = sort_key(sort.field);
+
+        let total = self
+            .collection
+            .count_documents(query.clone(), None)
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to count pills: {}", e);
+                RepositoryError::Unexpected
+            })?;
+
+        let query = match &page.cursor {
+            Some(cursor) => after_cursor(query, sort_key, sort.direction, cursor)
+                .ok_or(RepositoryError::InvalidCursor)?,
+            None => query,
+        };
+
+        let options = FindOptions::builder()
+            .sort(sort_document(sort_key, sort.direction))
+            .limit(i64::from(page.limit) + 1)
+            .build();
+
+        let pill_docs = self.collection.find(query, options).await.map_err(|e| {
+            tracing::error!("Repository: Failed to find pills page: {}", e);
             RepositoryError::Unexpected
         })?;
 
-        let pill_docs: Vec<PillDocument> = cursor.try_collect().await.map_err(|e| {
-            tracing::error!("Repository: Failed to collect pills from cursor: {}", e);
+        let pills: Result<Vec<Pill>, _> = pill_docs.into_iter().map(|doc| doc.try_into()).collect();
+
+        let mut pills = pills.map_err(|e| {
+            tracing::error!("Repository: Failed to deserialize pills: {}", e);
             RepositoryError::Unexpected
         })?;
 
-        let pills: Result<Vec<Pill>, _> = pill_docs
-            .into_iter()
-            .map(|doc| doc.try_into())
-            .collect();
+        let next_cursor = if pills.len() > page.limit as usize {
+            pills.truncate(page.limit as usize);
+            pills.last().map(|p| sort.cursor_after(p).encode())
+        } else {
+            None
+        };
+
+        tracing::info!(
+            "Repository: Found {} pills (page of {} total)",
+            pills.len(),
+            total
+        );
+        Ok(Page {
+            items: pills,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn count(&self) -> Result<u64, RepositoryError> {
+        self.collection
+            .estimated_document_count()
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to count pills: {}", e);
+                RepositoryError::Unexpected
+            })
+    }
+
+    async fn find_by_ids(&self, ids: &[PillId]) -> Result<Vec<Pill>, RepositoryError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let filter = doc! { "_id": { "$in": id_strings } };
+
+        let pill_docs = self.collection.find(filter, None).await.map_err(|e| {
+            tracing::error!("Repository: Failed to find pills by ids: {}", e);
+            RepositoryError::Unexpected
+        })?;
+
+        let pills: Result<Vec<Pill>, _> = pill_docs.into_iter().map(|doc| doc.try_into()).collect();
 
         let pills = pills.map_err(|e| {
             tracing::error!("Repository: Failed to deserialize pills: {}", e);
             RepositoryError::Unexpected
         })?;
 
-        tracing::info!("Repository: Found {} pills", pills.len());
+        tracing::debug!(
+            "Repository: Found {} of {} requested pills",
+            pills.len(),
+            ids.len()
+        );
         Ok(pills)
     }
+
+    async fn delete(&self, id: PillId) -> Result<(), RepositoryError> {
+        let filter = doc! { "_id": id.to_string() };
+
+        let result = self
+            .collection
+            .delete_one(filter, None)
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to delete pill {}: {}", id, e);
+                RepositoryError::Unexpected
+            })?;
+
+        if result.deleted_count == 0 {
+            tracing::debug!("Repository: Pill {} not found for deletion", id);
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::info!("Repository: Pill {} deleted successfully", id);
+        Ok(())
+    }
 }
@@ -0,0 +1,290 @@
+use async_trait::async_trait;
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, OptionalExtension, Row};
+
+use crate::database::sqlite::{after_cursor, cursor_value, order_by, SqliteDatabase};
+use crate::database::DatabaseError;
+use crate::pagination::{Page, PageRequest};
+use crate::pills::application::{PillRepository, RepositoryError};
+use crate::pills::domain::{Pill, PillFilter, PillId, PillSort, PillSortField};
+
+/// A `pills` row as read from SQLite.
+struct PillRow {
+    id: String,
+    title: String,
+    content: String,
+    version: i64,
+}
+
+impl PillRow {
+    fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            title: row.get("title")?,
+            content: row.get("content")?,
+            version: row.get("version")?,
+        })
+    }
+}
+
+impl TryFrom<PillRow> for Pill {
+    type Error = uuid::Error;
+
+    fn try_from(row: PillRow) -> Result<Self, Self::Error> {
+        let id = row.id.parse::<PillId>()?;
+        Ok(Pill::create(id, row.title, row.content).with_version(row.version as u64))
+    }
+}
+
+fn to_pills(rows: Vec<PillRow>) -> Result<Vec<Pill>, RepositoryError> {
+    let pills: Result<Vec<Pill>, _> = rows.into_iter().map(Pill::try_from).collect();
+
+    pills.map_err(|e| {
+        tracing::error!("Repository: Failed to deserialize pills: {}", e);
+        RepositoryError::Unexpected
+    })
+}
+
+fn unexpected(action: &str) -> impl FnOnce(DatabaseError) -> RepositoryError + '_ {
+    move |e| {
+        tracing::error!("Repository: Failed to {}: {}", action, e);
+        RepositoryError::Unexpected
+    }
+}
+
+fn sort_column(field: PillSortField) -> &'static str {
+    match field {
+        PillSortField::Title => "title",
+    }
+}
+
+/// Builds the `WHERE` conditions for `filter` and their parameters.
+fn filter_clause(filter: &PillFilter) -> (Vec<String>, Vec<SqlValue>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+
+    // substr keeps the comparison case-sensitive, like the MongoDB prefix regex.
+    if let Some(prefix) = &filter.title_prefix {
+        conditions.push("substr(title, 1, length(?)) = ?".to_string());
+        params.push(SqlValue::Text(prefix.clone()));
+        params.push(SqlValue::Text(prefix.clone()));
+    }
+
+    (conditions, params)
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+pub struct SqlitePillRepository {
+    database: SqliteDatabase,
+}
+
+impl SqlitePillRepository {
+    pub fn new(database: &SqliteDatabase) -> Self {
+        Self {
+            database: database.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl PillRepository for SqlitePillRepository {
+    async fn save(&self, pill: &mut Pill) -> Result<(), RepositoryError> {
+        let id = pill.id().to_string();
+        let title = pill.title().to_string();
+        let content = pill.content().to_string();
+        let expected = pill.version() as i64;
+
+        // Only a new pill may be inserted; an existing row is updated only while
+        // it is still at the expected version.
+        let changed = self
+            .database
+            .call(move |connection| {
+                if expected == 0 {
+                    connection.execute(
+                        "INSERT INTO pills (id, title, content, version) VALUES (?1, ?2, ?3, 1)
+                         ON CONFLICT (id) DO UPDATE
+                         SET title = excluded.title, content = excluded.content, version = 1
+                         WHERE pills.version = 0",
+                        params![id, title, content],
+                    )
+                } else {
+                    connection.execute(
+                        "UPDATE pills SET title = ?2, content = ?3, version = ?4 + 1
+                         WHERE id = ?1 AND version = ?4",
+                        params![id, title, content, expected],
+                    )
+                }
+            })
+            .await
+            .map_err(unexpected("save pill"))?;
+
+        if changed == 0 {
+            tracing::warn!(
+                "Repository: Pill {} is no longer at version {}",
+                pill.id(),
+                expected
+            );
+            return Err(RepositoryError::ConcurrencyConflict);
+        }
+        pill.set_version(expected as u64 + 1);
+
+        tracing::info!(
+            "Repository: Pill {} saved at version {}",
+            pill.id(),
+            pill.version()
+        );
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: PillId) -> Result<Option<Pill>, RepositoryError> {
+        let id_string = id.to_string();
+
+        let row = self
+            .database
+            .call(move |connection| {
+                connection
+                    .query_row(
+                        "SELECT id, title, content, version FROM pills WHERE id = ?1",
+                        params![id_string],
+                        PillRow::from_row,
+                    )
+                    .optional()
+            })
+            .await
+            .map_err(unexpected("find pill"))?;
+
+        let pill = to_pills(row.into_iter().collect())?.pop();
+
+        match &pill {
+            Some(_) => tracing::debug!("Repository: Found pill {}", id),
+            None => tracing::debug!("Repository: Pill {} not found", id),
+        }
+
+        Ok(pill)
+    }
+
+    async fn find_page(
+        &self,
+        filter: &PillFilter,
+        sort: PillSort,
+        page: &PageRequest,
+    ) -> Result<Page<Pill>, RepositoryError> {
+        let column = sort_column(sort.field);
+        let (mut conditions, mut params) = filter_clause(filter);
+        let count_sql = format!("SELECT COUNT(*) FROM pills {}", where_clause(&conditions));
+        let count_params = params.clone();
+
+        if let Some(cursor) = &page.cursor {
+            let value = cursor_value(cursor).ok_or(RepositoryError::InvalidCursor)?;
+            conditions.push(after_cursor(column, sort.direction));
+            params.extend([value.clone(), value, SqlValue::Text(cursor.id.clone())]);
+        }
+        params.push(SqlValue::Integer(i64::from(page.limit) + 1));
+
+        let select_sql = format!(
+            "SELECT id, title, content, version FROM pills {} {} LIMIT ?",
+            where_clause(&conditions),
+            order_by(column, sort.direction)
+        );
+
+        let (total, rows) = self
+            .database
+            .call(move |connection| {
+                let total: u64 =
+                    connection
+                        .query_row(&count_sql, params_from_iter(count_params), |row| row.get(0))?;
+                let mut statement = connection.prepare(&select_sql)?;
+                let rows = statement
+                    .query_map(params_from_iter(params), PillRow::from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok((total, rows))
+            })
+            .await
+            .map_err(unexpected("load pills page"))?;
+
+        let mut pills = to_pills(rows)?;
+        let next_cursor = if pills.len() > page.limit as usize {
+            pills.truncate(page.limit as usize);
+            pills.last().map(|p| sort.cursor_after(p).encode())
+        } else {
+            None
+        };
+
+        tracing::info!(
+            "Repository: Found {} pills (page of {} total)",
+            pills.len(),
+            total
+        );
+        Ok(Page {
+            items: pills,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn count(&self) -> Result<u64, RepositoryError> {
+        self.database
+            .call(|connection| {
+                connection.query_row("SELECT COUNT(*) FROM pills", [], |row| row.get(0))
+            })
+            .await
+            .map_err(unexpected("count pills"))
+    }
+
+    async fn find_by_ids(&self, ids: &[PillId]) -> Result<Vec<Pill>, RepositoryError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let id_strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let placeholders = vec!["?"; id_strings.len()].join(", ");
+        let sql =
+            format!("SELECT id, title, content, version FROM pills WHERE id IN ({placeholders})");
+
+        let rows = self
+            .database
+            .call(move |connection| {
+                let mut statement = connection.prepare(&sql)?;
+                let rows = statement
+                    .query_map(params_from_iter(id_strings), PillRow::from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(rows)
+            })
+            .await
+            .map_err(unexpected("find pills by IDs"))?;
+
+        let pills = to_pills(rows)?;
+        tracing::debug!(
+            "Repository: Found {} of {} requested pills",
+            pills.len(),
+            ids.len()
+        );
+        Ok(pills)
+    }
+
+    async fn delete(&self, id: PillId) -> Result<(), RepositoryError> {
+        let id_string = id.to_string();
+
+        let deleted = self
+            .database
+            .call(move |connection| {
+                connection.execute("DELETE FROM pills WHERE id = ?1", params![id_string])
+            })
+            .await
+            .map_err(unexpected("delete pill"))?;
+
+        if deleted == 0 {
+            tracing::debug!("Repository: Pill {} not found for deletion", id);
+            return Err(RepositoryError::NotFound);
+        }
+
+        tracing::info!("Repository: Pill {} deleted successfully", id);
+        Ok(())
+    }
+}
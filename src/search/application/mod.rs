@@ -0,0 +1,3 @@
+pub mod query;
+
+pub use query::{SearchQuery, SearchQueryHandler};
@@ -0,0 +1,3 @@
+pub mod search_query_handler;
+
+pub use search_query_handler::{SearchQuery, SearchQueryHandler};
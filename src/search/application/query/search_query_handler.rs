@@ -0,0 +1,60 @@
+use crate::search::domain::{
+    contains_match, highlight_snippet, SearchHitKind, SearchRepository, SearchRepositoryError,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(rename = "type")]
+    pub kind: SearchHitKind,
+    pub id: String,
+    pub title: String,
+    pub score: f64,
+    pub snippet: String,
+}
+
+pub struct SearchQuery {
+    pub text: String,
+    pub limit: u32,
+}
+
+pub struct SearchQueryHandler {
+    repository: Arc<dyn SearchRepository>,
+}
+
+impl SearchQueryHandler {
+    pub fn new(repository: Arc<dyn SearchRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn handle(
+        &self,
+        query: SearchQuery,
+    ) -> Result<Vec<SearchHit>, SearchRepositoryError> {
+        let matches = self.repository.search(&query.text, query.limit).await?;
+
+        let hits = matches
+            .into_iter()
+            .map(|m| {
+                // Excerpt the body if a query term appears in it. Otherwise the
+                // title, which either holds the term or, for a match through
+                // stemming alone, is the best summary; the body if it's empty.
+                let source = if contains_match(&m.body, &query.text) || m.title.is_empty() {
+                    &m.body
+                } else {
+                    &m.title
+                };
+                SearchHit {
+                    snippet: highlight_snippet(source, &query.text),
+                    kind: m.kind,
+                    id: m.id,
+                    title: m.title,
+                    score: m.score,
+                }
+            })
+            .collect();
+
+        Ok(hits)
+    }
+}
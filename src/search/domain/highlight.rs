@@ -0,0 +1,80 @@
+const WORDS_BEFORE_MATCH: usize = 8;
+const SNIPPET_WORDS: usize = 30;
+
+/// Builds a short, HTML-escaped excerpt of `text` around the first word that
+/// matches one of the query terms, with matching words wrapped in `<mark>`.
+///
+/// Matching is case-insensitive and by prefix, so `borrow` highlights
+/// `Borrowing` the same way the text index's stemming would match it.
+pub fn highlight_snippet(text: &str, query: &str) -> String {
+    let terms = terms(query);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let is_match = |word: &str| matches_term(word, &terms);
+
+    let first_match = words.iter().position(|word| is_match(word)).unwrap_or(0);
+    let start = first_match.saturating_sub(WORDS_BEFORE_MATCH);
+    let end = (start + SNIPPET_WORDS).min(words.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str("… ");
+    }
+
+    for (i, word) in words[start..end].iter().enumerate() {
+        if i > 0 {
+            snippet.push(' ');
+        }
+        if is_match(word) {
+            snippet.push_str("<mark>");
+            snippet.push_str(&escape_html(word));
+            snippet.push_str("</mark>");
+        } else {
+            snippet.push_str(&escape_html(word));
+        }
+    }
+
+    if end < words.len() {
+        snippet.push_str(" …");
+    }
+    snippet
+}
+
+/// Whether any word of `text` would be highlighted for `query`.
+pub fn contains_match(text: &str, query: &str) -> bool {
+    let terms = terms(query);
+    text.split_whitespace()
+        .any(|word| matches_term(word, &terms))
+}
+
+fn terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(normalize)
+        .filter(|term| !term.is_empty())
+        .collect()
+}
+
+fn matches_term(word: &str, terms: &[String]) -> bool {
+    let word = normalize(word);
+    !word.is_empty() && terms.iter().any(|term| word.starts_with(term.as_str()))
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,6 @@
+pub mod highlight;
+pub mod relevance;
+pub mod search_repository;
+
+pub use highlight::{contains_match, highlight_snippet};
+pub use search_repository::{SearchHitKind, SearchRepository, SearchRepositoryError, TextMatch};
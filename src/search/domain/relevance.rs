@@ -0,0 +1,25 @@
+/// Lowercased words of `text`, split on anything that isn't alphanumeric.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Relevance of `text` for the query `terms`, for backends without a text index.
+///
+/// Words match a term by case-insensitive prefix, standing in for the text
+/// index's stemming, and the score is the share of words that match, so short
+/// fields rank higher as they do with MongoDB's text score.
+pub fn field_score(text: &str, terms: &[String]) -> f64 {
+    let words = words(text);
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let matching = words
+        .iter()
+        .filter(|word| terms.iter().any(|term| word.starts_with(term.as_str())))
+        .count();
+    matching as f64 / words.len() as f64
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::search::domain::relevance;
+
+#[derive(Error, Debug, Clone)]
+pub enum SearchRepositoryError {
+    #[error("An unexpected error occurred")]
+    Unexpected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchHitKind {
+    Pill,
+    Course,
+}
+
+/// A document matching a full-text query, with the text it was matched against.
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    pub kind: SearchHitKind,
+    pub id: String,
+    pub title: String,
+    /// Pill content or course description.
+    pub body: String,
+    /// Relevance as reported by the text index; higher is better.
+    pub score: f64,
+}
+
+impl TextMatch {
+    /// Scores a document with [`relevance::field_score`] for backends without a
+    /// text index. Returns `None` if neither the title nor the body matches.
+    pub fn scored(
+        kind: SearchHitKind,
+        id: String,
+        title: String,
+        body: String,
+        terms: &[String],
+    ) -> Option<Self> {
+        let score = relevance::field_score(&title, terms) + relevance::field_score(&body, terms);
+        (score > 0.0).then_some(Self {
+            kind,
+            id,
+            title,
+            body,
+            score,
+        })
+    }
+
+    /// Orders matches best first and keeps the top `limit`.
+    pub fn rank(mut matches: Vec<TextMatch>, limit: u32) -> Vec<TextMatch> {
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit as usize);
+        matches
+    }
+}
+
+#[async_trait]
+pub trait SearchRepository: Send + Sync {
+    /// Returns up to `limit` matches from pills and courses, best first.
+    async fn search(&self, text: &str, limit: u32)
+        -> Result<Vec<TextMatch>, SearchRepositoryError>;
+}
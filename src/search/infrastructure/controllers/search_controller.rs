@@ -0,0 +1,38 @@
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api::{ApiError, ApiQuery};
+use crate::pagination::page_size;
+use crate::search::application::{SearchQuery, SearchQueryHandler};
+
+#[derive(Deserialize)]
+pub struct SearchParams {
+    q: Option<String>,
+    limit: Option<u32>,
+}
+
+pub async fn search_controller(
+    State(handler): State<Arc<SearchQueryHandler>>,
+    ApiQuery(params): ApiQuery<SearchParams>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let text = params.q.unwrap_or_default().trim().to_string();
+    if text.is_empty() {
+        return Err(ApiError::bad_request(
+            "missing_query",
+            "Query parameter 'q' is required",
+        ));
+    }
+
+    let query = SearchQuery {
+        text: text.clone(),
+        limit: page_size(params.limit)?,
+    };
+
+    let hits = handler.handle(query).await?;
+
+    Ok(Json(serde_json::json!({
+        "query": text,
+        "hits": hits,
+    })))
+}
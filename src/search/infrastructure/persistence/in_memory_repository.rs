@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::courses::infrastructure::persistence::in_memory_repository::InMemoryCourseRepository;
+use crate::pills::infrastructure::persistense::in_memory_repository::InMemoryPillRepository;
+use crate::search::domain::relevance::words;
+use crate::search::domain::{SearchHitKind, SearchRepository, SearchRepositoryError, TextMatch};
+
+/// Searches the in-memory pill and course stores, scoring every document with
+/// `relevance::field_score`.
+pub struct InMemorySearchRepository {
+    pills: Arc<InMemoryPillRepository>,
+    courses: Arc<InMemoryCourseRepository>,
+}
+
+impl InMemorySearchRepository {
+    pub fn new(pills: Arc<InMemoryPillRepository>, courses: Arc<InMemoryCourseRepository>) -> Self {
+        Self { pills, courses }
+    }
+}
+
+#[async_trait]
+impl SearchRepository for InMemorySearchRepository {
+    async fn search(
+        &self,
+        text: &str,
+        limit: u32,
+    ) -> Result<Vec<TextMatch>, SearchRepositoryError> {
+        let terms = words(text);
+
+        let pills = self
+            .pills
+            .all()
+            .map_err(|_| SearchRepositoryError::Unexpected)?;
+        let courses = self
+            .courses
+            .all()
+            .map_err(|_| SearchRepositoryError::Unexpected)?;
+
+        let pill_matches = pills.into_iter().filter_map(|pill| {
+            TextMatch::scored(
+                SearchHitKind::Pill,
+                pill.id().to_string(),
+                pill.title().to_string(),
+                pill.content().to_string(),
+                &terms,
+            )
+        });
+        let course_matches = courses.into_iter().filter_map(|course| {
+            TextMatch::scored(
+                SearchHitKind::Course,
+                course.id().to_string(),
+                course.title().to_string(),
+                course.description().to_string(),
+                &terms,
+            )
+        });
+
+        let matches = TextMatch::rank(pill_matches.chain(course_matches).collect(), limit);
+
+        tracing::info!(
+            "Repository: In-memory text search for '{}' returned {} matches",
+            text,
+            matches.len()
+        );
+        Ok(matches)
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{doc, Document},
+    options::FindOptions,
+    Collection, Database,
+};
+
+use crate::search::domain::{SearchHitKind, SearchRepository, SearchRepositoryError, TextMatch};
+
+/// Searches the `pills` and `courses` collections through the text indexes
+/// created by `database::indexes::create_text_indexes`.
+pub struct MongoDbSearchRepository {
+    pills: Collection<Document>,
+    courses: Collection<Document>,
+}
+
+impl MongoDbSearchRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            pills: database.collection::<Document>("pills"),
+            courses: database.collection::<Document>("courses"),
+        }
+    }
+
+    async fn search_collection(
+        &self,
+        collection: &Collection<Document>,
+        kind: SearchHitKind,
+        body_field: &str,
+        text: &str,
+        limit: u32,
+    ) -> Result<Vec<TextMatch>, SearchRepositoryError> {
+        let filter = doc! { "$text": { "$search": text } };
+        let options = FindOptions::builder()
+            .projection(doc! {
+                "title": 1,
+                body_field: 1,
+                "score": { "$meta": "textScore" },
+            })
+            .sort(doc! { "score": { "$meta": "textScore" } })
+            .limit(i64::from(limit))
+            .build();
+
+        let cursor = collection.find(filter, options).await.map_err(|e| {
+            tracing::error!(
+                "Repository: Failed to run text search on {}: {}",
+                collection.name(),
+                e
+            );
+            SearchRepositoryError::Unexpected
+        })?;
+
+        let docs: Vec<Document> = cursor.try_collect().await.map_err(|e| {
+            tracing::error!(
+                "Repository: Failed to collect text search results from {}: {}",
+                collection.name(),
+                e
+            );
+            SearchRepositoryError::Unexpected
+        })?;
+
+        let matches = docs
+            .into_iter()
+            .filter_map(|doc| {
+                let id = doc.get_str("_id").ok()?.to_string();
+                Some(TextMatch {
+                    kind,
+                    id,
+                    title: doc.get_str("title").unwrap_or_default().to_string(),
+                    body: doc.get_str(body_field).unwrap_or_default().to_string(),
+                    score: doc.get_f64("score").unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        Ok(matches)
+    }
+}
+
+#[async_trait]
+impl SearchRepository for MongoDbSearchRepository {
+    async fn search(
+        &self,
+        text: &str,
+        limit: u32,
+    ) -> Result<Vec<TextMatch>, SearchRepositoryError> {
+        let (pills, courses) = futures::try_join!(
+            self.search_collection(&self.pills, SearchHitKind::Pill, "content", text, limit),
+            self.search_collection(
+                &self.courses,
+                SearchHitKind::Course,
+                "description",
+                text,
+                limit
+            ),
+        )?;
+
+        let mut matches: Vec<TextMatch> = pills.into_iter().chain(courses).collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit as usize);
+
+        tracing::info!(
+            "Repository: Text search for '{}' returned {} matches",
+            text,
+            matches.len()
+        );
+        Ok(matches)
+    }
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use rusqlite::{params_from_iter, types::Value as SqlValue};
+
+use crate::database::sqlite::SqliteDatabase;
+use crate::search::domain::relevance::words;
+use crate::search::domain::{SearchHitKind, SearchRepository, SearchRepositoryError, TextMatch};
+
+/// Searches the SQLite `pills` and `courses` tables. `LIKE` narrows the rows to
+/// those containing a query term and `relevance::field_score` ranks them.
+pub struct SqliteSearchRepository {
+    database: SqliteDatabase,
+}
+
+impl SqliteSearchRepository {
+    pub fn new(database: &SqliteDatabase) -> Self {
+        Self {
+            database: database.clone(),
+        }
+    }
+}
+
+/// `SELECT` over `table` for rows whose title or body contains any of `terms`.
+fn candidates_sql(table: &str, body_column: &str, terms: usize) -> String {
+    let conditions =
+        vec![format!("title LIKE ? ESCAPE '\\' OR {body_column} LIKE ? ESCAPE '\\'"); terms];
+    format!(
+        "SELECT id, title, {body_column} FROM {table} WHERE {}",
+        conditions.join(" OR ")
+    )
+}
+
+fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+#[async_trait]
+impl SearchRepository for SqliteSearchRepository {
+    async fn search(
+        &self,
+        text: &str,
+        limit: u32,
+    ) -> Result<Vec<TextMatch>, SearchRepositoryError> {
+        let terms = words(text);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let params: Vec<SqlValue> = terms
+            .iter()
+            .flat_map(|term| {
+                let pattern = like_pattern(term);
+                [SqlValue::Text(pattern.clone()), SqlValue::Text(pattern)]
+            })
+            .collect();
+        let queries = [
+            (
+                SearchHitKind::Pill,
+                candidates_sql("pills", "content", terms.len()),
+            ),
+            (
+                SearchHitKind::Course,
+                candidates_sql("courses", "description", terms.len()),
+            ),
+        ];
+
+        let rows = self
+            .database
+            .call(move |connection| {
+                let mut rows = Vec::new();
+                for (kind, sql) in queries {
+                    let mut statement = connection.prepare(&sql)?;
+                    let found = statement
+                        .query_map(params_from_iter(params.iter()), |row| {
+                            Ok((
+                                kind,
+                                row.get::<_, String>(0)?,
+                                row.get::<_, String>(1)?,
+                                row.get::<_, String>(2)?,
+                            ))
+                        })?
+                        .collect::<rusqlite::Result<Vec<_>>>()?;
+                    rows.extend(found);
+                }
+                Ok(rows)
+            })
+            .await
+            .map_err(|e| {
+                tracing::error!("Repository: Failed to run SQLite text search: {}", e);
+                SearchRepositoryError::Unexpected
+            })?;
+
+        let matches = rows
+            .into_iter()
+            .filter_map(|(kind, id, title, body)| TextMatch::scored(kind, id, title, body, &terms))
+            .collect();
+        let matches = TextMatch::rank(matches, limit);
+
+        tracing::info!(
+            "Repository: Text search for '{}' returned {} matches",
+            text,
+            matches.len()
+        );
+        Ok(matches)
+    }
+}
@@ -0,0 +1,79 @@
+use axum::Router;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::config::ServerSettings;
+use crate::health::HealthService;
+
+/// Serves `app` until SIGINT or SIGTERM, then shuts down in stages:
+///
+/// 1. `/health/ready` starts returning 503 so load balancers stop routing here.
+/// 2. Requests are still served for `shutdown_drain` while they notice.
+/// 3. The listener closes and in-flight requests get up to `shutdown_timeout`
+///    to finish before the server stops waiting for them.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    health_service: Arc<HealthService>,
+    settings: &ServerSettings,
+) -> std::io::Result<()> {
+    let drain = settings.shutdown_drain;
+    let timeout = settings.shutdown_timeout;
+    let (drained_tx, drained_rx) = oneshot::channel();
+
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        signal().await;
+
+        health_service.start_shutdown();
+        tracing::info!(
+            "Shutdown: Marked not ready, serving for {:?} while traffic drains",
+            drain
+        );
+        tokio::time::sleep(drain).await;
+
+        tracing::info!("Shutdown: Stopped accepting connections, waiting for in-flight requests");
+        let _ = drained_tx.send(());
+    });
+
+    tokio::select! {
+        result = server => result,
+        _ = async {
+            match drained_rx.await {
+                Ok(()) => tokio::time::sleep(timeout).await,
+                Err(_) => std::future::pending().await,
+            }
+        } => {
+            tracing::warn!(
+                "Shutdown: In-flight requests still running after {:?}, stopping anyway",
+                timeout
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Resolves on Ctrl+C (SIGINT) or, on Unix, SIGTERM.
+async fn signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Shutdown: Received SIGINT"),
+        _ = terminate => tracing::info!("Shutdown: Received SIGTERM"),
+    }
+}
@@ -0,0 +1,240 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{DatabaseSettings, HealthSettings};
+use crate::courses::domain::CourseRepository;
+use crate::courses::infrastructure::persistence::in_memory_repository::InMemoryCourseRepository;
+use crate::courses::infrastructure::persistence::mongodb_repository::MongoDbCourseRepository;
+use crate::courses::infrastructure::persistence::sqlite_repository::SqliteCourseRepository;
+use crate::database::connector::{self, ConnectionState};
+use crate::database::sqlite::{SqliteConfig, SqliteUnitOfWork};
+use crate::database::transaction::MongoUnitOfWork;
+use crate::database::DatabaseConfig;
+use crate::health::checks::{DatabaseCheck, DiskSpaceCheck, IndexCheck, PoolCheck};
+use crate::health::probes::{InMemoryProbe, MongoProbe};
+use crate::health::HealthService;
+use crate::metrics::repositories::{instrument, InstrumentedUnitOfWork};
+use crate::metrics::Metrics;
+use crate::pills::domain::PillRepository;
+use crate::pills::infrastructure::persistense::in_memory_repository::InMemoryPillRepository;
+use crate::pills::infrastructure::persistense::mongodb_repository::MongoDbPillRepository;
+use crate::pills::infrastructure::persistense::sqlite_repository::SqlitePillRepository;
+use crate::search::domain::SearchRepository;
+use crate::search::infrastructure::persistence::in_memory_repository::InMemorySearchRepository;
+use crate::search::infrastructure::persistence::mongodb_repository::MongoDbSearchRepository;
+use crate::search::infrastructure::persistence::sqlite_repository::SqliteSearchRepository;
+use crate::unit_of_work::{NoopUnitOfWork, Repositories, UnitOfWork};
+
+/// How long closing the MongoDB client may wait for operations still in use.
+const MONGO_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where pills and courses are stored, selected with `STORAGE_BACKEND` or `database.backend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    #[default]
+    MongoDb,
+    /// A single SQLite file, for deployments that can't run MongoDB.
+    Sqlite,
+    /// Process-local storage for demos and frontend work; nothing survives a restart.
+    Memory,
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mongodb" | "mongo" => Ok(Self::MongoDb),
+            "sqlite" => Ok(Self::Sqlite),
+            "memory" | "in-memory" => Ok(Self::Memory),
+            other => Err(format!(
+                "Unknown STORAGE_BACKEND '{}', expected 'mongodb', 'sqlite' or 'memory'",
+                other
+            )),
+        }
+    }
+}
+
+/// The repositories, unit of work, health service and metrics backing one
+/// storage backend. Pill and course repository operations are recorded in
+/// `metrics`, including those made inside a unit of work.
+pub struct Storage {
+    pub pill_repository: Arc<dyn PillRepository>,
+    pub course_repository: Arc<dyn CourseRepository>,
+    pub search_repository: Arc<dyn SearchRepository>,
+    pub unit_of_work: Arc<dyn UnitOfWork>,
+    pub health_service: Arc<HealthService>,
+    pub metrics: Arc<Metrics>,
+    /// Closed by `close`; only set for MongoDB.
+    mongo_client: Option<mongodb::Client>,
+}
+
+impl Storage {
+    pub async fn init(database: &DatabaseSettings, health: &HealthSettings) -> Self {
+        let storage = match database.backend {
+            StorageBackend::MongoDb => Self::mongodb(database, health).await,
+            StorageBackend::Sqlite => Self::sqlite(database, health).await,
+            StorageBackend::Memory => Self::in_memory(health),
+        };
+        storage.instrumented()
+    }
+
+    fn instrumented(self) -> Self {
+        let repositories = instrument(
+            Repositories {
+                pill_repository: self.pill_repository,
+                course_repository: self.course_repository,
+            },
+            &self.metrics,
+        );
+
+        Self {
+            pill_repository: repositories.pill_repository,
+            course_repository: repositories.course_repository,
+            unit_of_work: Arc::new(InstrumentedUnitOfWork::new(
+                self.unit_of_work,
+                self.metrics.clone(),
+            )),
+            ..self
+        }
+    }
+
+    /// Doesn't wait for MongoDB: the connector keeps trying in the background
+    /// and `/health/ready` reports not ready until it succeeds.
+    async fn mongodb(settings: &DatabaseSettings, health: &HealthSettings) -> Self {
+        let db_config = DatabaseConfig::new(settings)
+            .await
+            .expect("Invalid MongoDB configuration");
+
+        let database = db_config.get_database().clone();
+        let client = db_config.get_client().clone();
+        let pool_monitor = db_config.pool_monitor.clone();
+        let pill_repository: Arc<dyn PillRepository> =
+            Arc::new(MongoDbPillRepository::new(&database));
+        let course_repository: Arc<dyn CourseRepository> =
+            Arc::new(MongoDbCourseRepository::new(&database));
+
+        let unit_of_work = Arc::new(MongoUnitOfWork::new(
+            client.clone(),
+            database.clone(),
+            Repositories {
+                pill_repository: pill_repository.clone(),
+                course_repository: course_repository.clone(),
+            },
+        ));
+
+        let metrics = Metrics::new(pill_repository.clone(), course_repository.clone())
+            .with_pool(pool_monitor.clone(), settings.max_pool_size);
+
+        let connection = ConnectionState::default();
+        connector::spawn(
+            db_config,
+            unit_of_work.clone(),
+            connection.clone(),
+            settings.reconnect_max_backoff,
+        );
+
+        Self {
+            pill_repository,
+            course_repository,
+            search_repository: Arc::new(MongoDbSearchRepository::new(&database)),
+            unit_of_work,
+            health_service: Arc::new(
+                HealthService::new(health.failure_threshold)
+                    .with_check(
+                        Arc::new(DatabaseCheck::new(Arc::new(MongoProbe::new(
+                            database.clone(),
+                            connection.clone(),
+                        )))),
+                        health.timeout,
+                    )
+                    .with_check(
+                        Arc::new(IndexCheck::new(database, connection)),
+                        health.timeout,
+                    )
+                    .with_check(
+                        Arc::new(PoolCheck::new(pool_monitor, settings.max_pool_size)),
+                        health.timeout,
+                    ),
+            ),
+            metrics: Arc::new(metrics),
+            mongo_client: Some(client),
+        }
+    }
+
+    async fn sqlite(settings: &DatabaseSettings, health: &HealthSettings) -> Self {
+        let sqlite_config =
+            SqliteConfig::new(&settings.sqlite_path).expect("Failed to open SQLite database");
+
+        sqlite_config
+            .initialize_schema()
+            .await
+            .expect("Failed to create SQLite schema");
+
+        let database = sqlite_config.get_database();
+        let pill_repository: Arc<dyn PillRepository> =
+            Arc::new(SqlitePillRepository::new(database));
+        let course_repository: Arc<dyn CourseRepository> =
+            Arc::new(SqliteCourseRepository::new(database));
+
+        Self {
+            pill_repository: pill_repository.clone(),
+            course_repository: course_repository.clone(),
+            search_repository: Arc::new(SqliteSearchRepository::new(database)),
+            unit_of_work: Arc::new(SqliteUnitOfWork::new(database)),
+            health_service: Arc::new(
+                HealthService::new(health.failure_threshold)
+                    .with_check(
+                        Arc::new(DatabaseCheck::new(Arc::new(database.clone()))),
+                        health.timeout,
+                    )
+                    .with_check(
+                        Arc::new(DiskSpaceCheck::new(&settings.sqlite_path)),
+                        health.timeout,
+                    ),
+            ),
+            metrics: Arc::new(Metrics::new(pill_repository, course_repository)),
+            mongo_client: None,
+        }
+    }
+
+    fn in_memory(health: &HealthSettings) -> Self {
+        tracing::warn!("Storage: Using in-memory storage, data will be lost on restart");
+
+        let pills = Arc::new(InMemoryPillRepository::new());
+        let courses = Arc::new(InMemoryCourseRepository::new());
+
+        Self {
+            pill_repository: pills.clone(),
+            course_repository: courses.clone(),
+            search_repository: Arc::new(InMemorySearchRepository::new(
+                pills.clone(),
+                courses.clone(),
+            )),
+            unit_of_work: Arc::new(NoopUnitOfWork::new(Repositories {
+                pill_repository: pills.clone(),
+                course_repository: courses.clone(),
+            })),
+            health_service: Arc::new(HealthService::new(health.failure_threshold).with_check(
+                Arc::new(DatabaseCheck::new(Arc::new(InMemoryProbe))),
+                health.timeout,
+            )),
+            metrics: Arc::new(Metrics::new(pills, courses)),
+            mongo_client: None,
+        }
+    }
+
+    /// Closes database connections once the server has stopped. The SQLite
+    /// connection and in-memory data are simply dropped with the process.
+    pub async fn close(&self) {
+        if let Some(client) = &self.mongo_client {
+            // `shutdown` waits for sessions and cursors still in use, which only
+            // requests abandoned by the shutdown timeout would hold.
+            match tokio::time::timeout(MONGO_CLOSE_TIMEOUT, client.clone().shutdown()).await {
+                Ok(()) => tracing::info!("Shutdown: Closed MongoDB client"),
+                Err(_) => tracing::warn!("Shutdown: MongoDB client did not close in time"),
+            }
+        }
+    }
+}
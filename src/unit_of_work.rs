@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::courses::domain::{CourseRepository, CourseRepositoryError};
+use crate::database::DatabaseError;
+use crate::pills::domain::{PillRepository, RepositoryError};
+
+/// How many times a unit of work is attempted when the backend keeps reporting
+/// transient transaction errors, such as write conflicts with another transaction.
+pub const MAX_TRANSACTION_ATTEMPTS: usize = 3;
+
+/// The repositories a unit of work operates on. Inside a transaction every
+/// read and write made through them belongs to that transaction.
+#[derive(Clone)]
+pub struct Repositories {
+    pub pill_repository: Arc<dyn PillRepository>,
+    pub course_repository: Arc<dyn CourseRepository>,
+}
+
+#[async_trait]
+pub trait Transaction: Send + Sync {
+    fn repositories(&self) -> Repositories;
+    /// Whether an operation failed in a way that retrying the whole transaction may fix.
+    fn has_transient_error(&self) -> bool;
+    async fn commit(&self) -> Result<(), DatabaseError>;
+    async fn abort(&self);
+}
+
+/// Starts transactions for operations that change several aggregates at once.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, DatabaseError>;
+}
+
+impl dyn UnitOfWork {
+    /// Runs `work` in a transaction and commits it. On a transient transaction
+    /// error the transaction is aborted and `work` runs again from the start,
+    /// up to [`MAX_TRANSACTION_ATTEMPTS`] times, so it must not have side
+    /// effects outside the repositories it is given.
+    pub async fn run<T, E, F, Fut>(&self, work: F) -> Result<T, E>
+    where
+        F: Fn(Repositories) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, E>> + Send,
+        T: Send,
+        E: From<DatabaseError> + Send,
+    {
+        for attempt in 1..=MAX_TRANSACTION_ATTEMPTS {
+            let transaction = self.begin().await?;
+
+            match work(transaction.repositories()).await {
+                Ok(value) => match transaction.commit().await {
+                    Ok(()) => return Ok(value),
+                    Err(e) if !transaction.has_transient_error() => return Err(e.into()),
+                    Err(_) => {}
+                },
+                Err(e) => {
+                    transaction.abort().await;
+                    if !transaction.has_transient_error() {
+                        return Err(e);
+                    }
+                }
+            }
+
+            tracing::warn!(
+                "Unit of work: Transient transaction error on attempt {} of {}, retrying",
+                attempt,
+                MAX_TRANSACTION_ATTEMPTS
+            );
+        }
+
+        tracing::error!(
+            "Unit of work: Giving up after {} attempts",
+            MAX_TRANSACTION_ATTEMPTS
+        );
+        Err(DatabaseError::TransactionFailed.into())
+    }
+}
+
+/// Fallback for backends without multi-document transactions: work runs
+/// directly against the shared repositories, so a failure part-way through
+/// leaves the changes made until then in place.
+pub struct NoopUnitOfWork {
+    repositories: Repositories,
+}
+
+impl NoopUnitOfWork {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for NoopUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, DatabaseError> {
+        Ok(Box::new(NoopTransaction {
+            repositories: self.repositories.clone(),
+        }))
+    }
+}
+
+struct NoopTransaction {
+    repositories: Repositories,
+}
+
+#[async_trait]
+impl Transaction for NoopTransaction {
+    fn repositories(&self) -> Repositories {
+        self.repositories.clone()
+    }
+
+    fn has_transient_error(&self) -> bool {
+        false
+    }
+
+    async fn commit(&self) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    async fn abort(&self) {}
+}
+
+impl From<DatabaseError> for RepositoryError {
+    fn from(error: DatabaseError) -> Self {
+        tracing::error!("Unit of work: Transaction failed: {}", error);
+        RepositoryError::Unexpected
+    }
+}
+
+impl From<DatabaseError> for CourseRepositoryError {
+    fn from(error: DatabaseError) -> Self {
+        tracing::error!("Unit of work: Transaction failed: {}", error);
+        CourseRepositoryError::Unexpected
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::fmt;
+
+/// A rule violated by a single input field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Every rule violation found while checking an input, so callers can report
+/// all offending fields at once instead of stopping at the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Adds an error unless `value` has non-whitespace content of at most `max_len` characters.
+    pub fn check_text(&mut self, field: &str, value: &str, max_len: usize) {
+        if value.trim().is_empty() {
+            self.add(field, "must not be blank");
+        } else if value.chars().count() > max_len {
+            self.add(field, format!("must be at most {} characters", max_len));
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .0
+            .iter()
+            .map(|e| format!("{} {}", e.field, e.message))
+            .collect();
+        write!(f, "{}", fields.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
@@ -98,9 +98,9 @@ make_request "GET" "/pills" "" "📋 Getting all pills"
 
 # Store pill IDs for later use (get them from the all pills response)
 all_pills_response=$(curl -s http://localhost:3000/pills)
-pill1_id=$(echo "$all_pills_response" | jq -r '.[0].id')
-pill2_id=$(echo "$all_pills_response" | jq -r '.[1].id')
-pill3_id=$(echo "$all_pills_response" | jq -r '.[2].id')
+pill1_id=$(echo "$all_pills_response" | jq -r '.items[0].id')
+pill2_id=$(echo "$all_pills_response" | jq -r '.items[1].id')
+pill3_id=$(echo "$all_pills_response" | jq -r '.items[2].id')
 
 echo -e "${YELLOW}Extracted Pill IDs:${NC}"
 echo -e "Pill 1: $pill1_id"
@@ -150,8 +150,8 @@ echo -e "${BLUE}=== Testing Multiple Pills Addition ===${NC}"
 echo ""
 
 # Get the first course ID (the one without pills initially)
-all_courses_response=$(curl -s http://localhost:3000/courses)
-first_course_id=$(echo "$all_courses_response" | jq -r '.[0].id')
+all_courses_response=$(curl -s "http://localhost:3000/courses?instructor=John%20Doe")
+first_course_id=$(echo "$all_courses_response" | jq -r '.items[0].id')
 
 echo -e "${YELLOW}Using first course ID for multiple pills test: $first_course_id${NC}"
 echo ""